@@ -0,0 +1,78 @@
+// src/beneficials.rs
+
+use crate::pests::{Pest, PestType};
+use serde::{Deserialize, Serialize};
+
+/// The helpful insects living on a tile, each as a population from 0.0 to 1.0.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub struct Beneficials {
+    /// Pollinators; drawn to flowering plants and raise their yield.
+    pub bees: f32,
+    /// Aphid hunters; drawn to aphids and whiteflies.
+    pub ladybugs: f32,
+    /// Generalist predators; drawn to flowers and to any pest.
+    pub lacewings: f32,
+}
+
+/// Share of the gap to a tile's attraction a population closes each tick
+/// while it is arriving and breeding.
+pub const SETTLE_RATE: f32 = 0.2;
+/// The same once there is less to draw them; insects linger a while after
+/// the flowers fade or the prey runs out.
+pub const DEPART_RATE: f32 = 0.05;
+/// Pollination a flowering plant gains per tick from a full bee population.
+pub const POLLINATION_RATE: f32 = 0.25;
+/// Extra yield from a fully pollinated plant.
+pub const POLLINATION_YIELD_BONUS: f32 = 0.5;
+
+impl Beneficials {
+    /// Moves each population towards what the tile offers it: flowers for
+    /// bees and lacewings, prey for ladybugs and lacewings.
+    pub fn settle(&mut self, flowering: bool, pest: Option<&Pest>) {
+        let flowers = if flowering { 1.0 } else { 0.0 };
+        let (aphid_prey, any_prey) = match pest {
+            Some(pest) if pest.pest_type == PestType::SpiderMites => (0.0, pest.infestation_level),
+            Some(pest) => (pest.infestation_level, pest.infestation_level),
+            None => (0.0, 0.0),
+        };
+        let settle = |population: &mut f32, attraction: f32| {
+            let gap = attraction.min(1.0) - *population;
+            *population += gap * if gap > 0.0 { SETTLE_RATE } else { DEPART_RATE };
+        };
+        settle(&mut self.bees, flowers);
+        settle(&mut self.ladybugs, 2.0 * aphid_prey + 0.1 * flowers);
+        settle(&mut self.lacewings, any_prey + 0.3 * flowers);
+    }
+
+    /// Infestation of `pest_type` eaten per tick.
+    pub fn predation(&self, pest_type: &PestType) -> f32 {
+        let ladybug_appetite = match pest_type {
+            PestType::Aphids => 0.15,
+            PestType::Whiteflies => 0.05,
+            PestType::SpiderMites => 0.0,
+        };
+        self.ladybugs * ladybug_appetite + self.lacewings * 0.08
+    }
+
+    /// Kills `share` of every population, as a spray drifting over the tile does.
+    pub fn harm(&mut self, share: f32) {
+        let survive = 1.0 - share.clamp(0.0, 1.0);
+        self.bees *= survive;
+        self.ladybugs *= survive;
+        self.lacewings *= survive;
+    }
+
+    /// A short summary such as "bees 0.80, lacewings 0.20" or "none".
+    pub fn describe(&self) -> String {
+        let parts: Vec<String> = [("bees", self.bees), ("ladybugs", self.ladybugs), ("lacewings", self.lacewings)]
+            .iter()
+            .filter(|(_, population)| *population >= 0.05)
+            .map(|(name, population)| format!("{} {:.2}", name, population))
+            .collect();
+        if parts.is_empty() {
+            "none".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// src/breeding.rs
+
+use crate::catalogue::{Category, Rarity};
+use crate::plant::Plant;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A species created by crossing two others. Its template plays the same
+/// part for it that a `catalogue::Species` template plays for a catalogued one.
+#[derive(Serialize, Deserialize)]
+pub struct Hybrid {
+    pub name: String,
+    /// The species crossed to create it, seed parent first.
+    pub parents: (String, String),
+    /// The tick of the first cross.
+    pub bred_at: u64,
+    pub template: Plant,
+    /// Taken from the seed parent.
+    #[serde(default)]
+    pub category: Category,
+}
+
+/// Tiles apart, in any direction, two plants may stand and still cross-pollinate.
+pub const POLLINATION_RANGE: u32 = 3;
+/// Seeds produced by one cross.
+pub const SEEDS_PER_CROSS: u32 = 3;
+/// Every hybrid is priced as a species this rare.
+pub const HYBRID_RARITY: Rarity = Rarity::Rare;
+
+/// The hybrid already bred from this pair of species, in either order.
+pub fn find_hybrid<'a>(hybrids: &'a BTreeMap<String, Hybrid>, first: &str, second: &str) -> Option<&'a Hybrid> {
+    hybrids.values().find(|hybrid| {
+        let (a, b) = (&hybrid.parents.0, &hybrid.parents.1);
+        (a == first && b == second) || (a == second && b == first)
+    })
+}
+
+/// A name for the cross of `mother` and `father`: the first word of one
+/// and the last word of the other, so "Crimson Bloom" x "Azure Fern" gives
+/// "Crimson Fern". A number is added if the name is already taken.
+pub fn hybrid_name(mother: &str, father: &str, taken: impl Fn(&str) -> bool) -> String {
+    let first = mother.split_whitespace().next().unwrap_or(mother);
+    let last = father.split_whitespace().last().unwrap_or(father);
+    let base = format!("{} {}", first, last);
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while name == mother || name == father || taken(&name) {
+        name = format!("{} {}", base, suffix);
+        suffix += 1;
+    }
+    name
+}
@@ -0,0 +1,280 @@
+// src/catalogue.rs
+
+use crate::breeding::Hybrid;
+use crate::economy::{self, Market};
+use crate::genome::Genome;
+use crate::plant::{Deficiency, LifeCycleStage, Plant, PlantGenetics};
+use crate::plant_definitions;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    Vegetable,
+    #[default]
+    Flower,
+    Tree,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+}
+
+impl Rarity {
+    /// How much more the produce of a species this rare is worth.
+    pub fn price_multiplier(&self) -> f32 {
+        match self {
+            Rarity::Common => 1.0,
+            Rarity::Uncommon => 1.5,
+            Rarity::Rare => 2.5,
+        }
+    }
+}
+
+/// One species as written in a catalogue file, e.g.
+/// `{"name": "Tomato", "category": "vegetable", "rarity": "common",
+/// "maturity_age": 10, "wither_time": 15, "growth_time": 8, "yield_range": [3, 8], ...}`
+/// with the rest of the `PlantGenetics` fields alongside.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SpeciesDefinition {
+    pub name: String,
+    pub category: Category,
+    #[serde(default)]
+    pub rarity: Rarity,
+    pub maturity_age: u32,
+    pub wither_time: u32,
+    #[serde(flatten)]
+    pub genetics: PlantGenetics,
+}
+
+impl SpeciesDefinition {
+    /// Why this definition cannot be used, if it cannot.
+    fn validate(&self) -> Result<(), String> {
+        let genetics = &self.genetics;
+        let problem = if self.name.trim().is_empty() {
+            Some("the name is empty".to_string())
+        } else if genetics.growth_time == 0 {
+            Some("growth_time must be at least 1".to_string())
+        } else if genetics.yield_range.0 > genetics.yield_range.1 {
+            Some("yield_range is the wrong way round".to_string())
+        } else if !(0.0..=1.0).contains(&genetics.ideal_moisture_range.0)
+            || !(0.0..=1.0).contains(&genetics.ideal_moisture_range.1)
+            || genetics.ideal_moisture_range.0 > genetics.ideal_moisture_range.1
+        {
+            Some("ideal_moisture_range must be an ascending range within 0.0..=1.0".to_string())
+        } else if genetics.preferred_ph_range.0 > genetics.preferred_ph_range.1 {
+            Some("preferred_ph_range is the wrong way round".to_string())
+        } else if [genetics.pest_resistance, genetics.disease_resistance, genetics.genetic_stability]
+            .iter()
+            .any(|value| !(0.0..=1.0).contains(value))
+        {
+            Some("resistances and genetic_stability must be within 0.0..=1.0".to_string())
+        } else if self.maturity_age >= self.wither_time {
+            Some("maturity_age must come before wither_time".to_string())
+        } else {
+            None
+        };
+        match problem {
+            Some(problem) => Err(format!("species '{}': {}", self.name, problem)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A species available to plant, with the template every individual of it
+/// is grown from.
+pub struct Species {
+    pub category: Category,
+    pub rarity: Rarity,
+    pub template: Plant,
+}
+
+/// Every species that can be planted, by name.
+pub struct Catalogue {
+    species: BTreeMap<String, Species>,
+    /// The definitions the catalogue was built from, as given.
+    definitions: Vec<SpeciesDefinition>,
+}
+
+impl Catalogue {
+    /// Checks every definition and builds the catalogue, or reports the first
+    /// invalid or duplicated species.
+    pub fn from_definitions(definitions: &[SpeciesDefinition]) -> Result<Catalogue, String> {
+        let mut species = BTreeMap::new();
+        for definition in definitions {
+            definition.validate()?;
+            let template = Plant {
+                species: definition.name.clone(),
+                genetics: definition.genetics.clone(),
+                life_cycle_stage: LifeCycleStage::Seed,
+                age: 0,
+                maturity_age: definition.maturity_age,
+                wither_time: definition.wither_time,
+                growth_progress: 0.0,
+                health: 1.0,
+                deficiency: Deficiency::default(),
+                pollination: 0.0,
+                genome: Genome::default(),
+                id: 0,
+                parents: Vec::new(),
+                generation: 0,
+            };
+            let entry = Species {
+                category: definition.category,
+                rarity: definition.rarity,
+                template,
+            };
+            if species.insert(definition.name.clone(), entry).is_some() {
+                return Err(format!("species '{}' is defined twice", definition.name));
+            }
+        }
+        if species.is_empty() {
+            return Err("the catalogue defines no species".to_string());
+        }
+        Ok(Catalogue { species, definitions: definitions.to_vec() })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Species> {
+        self.species.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Species> {
+        self.species.values()
+    }
+}
+
+/// A TOML catalogue, which needs a table at the top: one `[[species]]` per definition.
+#[derive(Deserialize)]
+struct TomlCatalogue {
+    species: Vec<SpeciesDefinition>,
+}
+
+/// Reads the species in a catalogue file: a JSON list of `SpeciesDefinition`s,
+/// or, for a `.toml` file, a `[[species]]` table for each.
+pub fn read_definitions(path: &str) -> Result<Vec<SpeciesDefinition>, String> {
+    let data = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    if path.ends_with(".toml") {
+        let catalogue: TomlCatalogue = toml::from_str(&data).map_err(|e| e.to_string())?;
+        Ok(catalogue.species)
+    } else {
+        serde_json::from_str(&data).map_err(|e| e.to_string())
+    }
+}
+
+static ACTIVE: OnceCell<Catalogue> = OnceCell::new();
+
+/// The catalogue in use: the one passed to `install`, or the built-in species.
+pub fn catalogue() -> &'static Catalogue {
+    ACTIVE.get_or_init(|| {
+        Catalogue::from_definitions(&plant_definitions::SPECIES).expect("the built-in species are valid")
+    })
+}
+
+/// Replaces the built-in species with `catalogue`. Must be called before
+/// anything looks a species up; returns `false` if that has already happened.
+pub fn install(catalogue: Catalogue) -> bool {
+    ACTIVE.set(catalogue).is_ok()
+}
+
+/// Puts the catalogue a save was played with in place. Fails if it is invalid,
+/// or if a different catalogue is already in use, since the save's species
+/// would then not be the ones it was played with.
+pub fn restore(definitions: &[SpeciesDefinition]) -> Result<(), String> {
+    let saved = Catalogue::from_definitions(definitions)?;
+    if ACTIVE.get_or_init(|| saved).definitions != definitions {
+        return Err("the game was played with a different species catalogue from the one in use; \
+            load it with the --catalogue it was played with, or none"
+            .to_string());
+    }
+    Ok(())
+}
+
+/// The category and rarity of `species`, whether catalogued or bred.
+fn classify(hybrids: &BTreeMap<String, Hybrid>, species: &str) -> Option<(Category, Rarity)> {
+    match catalogue().get(species) {
+        Some(entry) => Some((entry.category, entry.rarity)),
+        None => hybrids.get(species).map(|hybrid| (hybrid.category, crate::breeding::HYBRID_RARITY)),
+    }
+}
+
+/// A table of every species, catalogued and bred, optionally of one category only.
+pub fn list_species(hybrids: &BTreeMap<String, Hybrid>, market: &Market, category: Option<Category>) -> String {
+    let mut listing = String::from("Species\t\t\tCategory\tRarity\t\tGrowth\tYield\tPrice\n");
+    let templates = catalogue()
+        .iter()
+        .map(|entry| &entry.template)
+        .chain(hybrids.values().map(|hybrid| &hybrid.template));
+    for template in templates {
+        let Some((species_category, rarity)) = classify(hybrids, &template.species) else { continue };
+        if category.is_some_and(|category| category != species_category) {
+            continue;
+        }
+        let price = market.price(&template.species).map_or("-".to_string(), |price| format!("{:.2}", price));
+        listing.push_str(&format!(
+            "{}\t\t{:?}\t\t{:?}\t\t{}\t{}-{}\t{}\n",
+            template.species,
+            species_category,
+            rarity,
+            template.genetics.growth_time,
+            template.genetics.yield_range.0,
+            template.genetics.yield_range.1,
+            price
+        ));
+    }
+    listing
+}
+
+/// Every stat of one species, or `None` if there is no such species.
+pub fn show_species(hybrids: &BTreeMap<String, Hybrid>, market: &Market, species: &str) -> Option<String> {
+    let template = crate::plant::species_template(hybrids, species)?;
+    let (category, rarity) = classify(hybrids, species)?;
+    let genetics = &template.genetics;
+    let mut report = format!("{} ({:?}, {:?})", species, category, rarity);
+    if let Some(hybrid) = hybrids.get(species) {
+        report.push_str(&format!(
+            "\n  hybrid of {} x {}, first bred on tick {}",
+            hybrid.parents.0, hybrid.parents.1, hybrid.bred_at
+        ));
+    }
+    report.push_str(&format!(
+        "\n  growth time {} | matures at {} | withers at {}\
+         \n  yield {}-{} | moisture {:.2}-{:.2} | pH {:.1}-{:.1} | light {:.1}h | height {:.1}m\
+         \n  uses N {:.2} P {:.2} K {:.2}\
+         \n  pest res {:.2} | disease res {:.2} | stability {:.2}",
+        genetics.growth_time,
+        template.maturity_age,
+        template.wither_time,
+        genetics.yield_range.0,
+        genetics.yield_range.1,
+        genetics.ideal_moisture_range.0,
+        genetics.ideal_moisture_range.1,
+        genetics.preferred_ph_range.0,
+        genetics.preferred_ph_range.1,
+        genetics.light_req,
+        genetics.height,
+        genetics.nutrient_consumption.0,
+        genetics.nutrient_consumption.1,
+        genetics.nutrient_consumption.2,
+        genetics.pest_resistance,
+        genetics.disease_resistance,
+        genetics.genetic_stability
+    ));
+    let prices = [
+        ("produce", market.price(species)),
+        ("seeds", market.price(&economy::seed_item(species))),
+    ];
+    for (kind, price) in prices {
+        if let Some(price) = price {
+            report.push_str(&format!("\n  {} {:.2}", kind, price));
+        }
+    }
+    Some(report)
+}
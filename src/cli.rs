@@ -2,9 +2,23 @@
 
 use clap::Parser;
 
+use crate::catalogue::Category;
+use crate::economy::ItemKind;
+use crate::garden::{AmendmentKind, MAX_PLOT_SIDE};
+use crate::orders::Side;
+use crate::targeting::{AxisRange, TileFilter, TileSelection};
+use crate::treatment::Treatment;
+use crate::weather::Weather;
+
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 pub struct Args {
+    /// The save file to operate on; mutating commands write their result back to it
+    #[clap(long, global = true, default_value = "default_save.json")]
+    pub save: String,
+    /// A JSON or TOML species catalogue to use in place of the built-in species
+    #[clap(long, global = true)]
+    pub catalogue: Option<String>,
     #[clap(subcommand)]
     pub command: Commands,
 }
@@ -12,13 +26,17 @@ pub struct Args {
 #[derive(Parser, Debug)]
 pub enum Commands {
     /// Starts a new game
-    New,
-    /// Saves the game state
+    New {
+        /// Seed for the simulation RNG; replaying the same commands with the same seed gives identical results
+        #[clap(long)]
+        seed: Option<u64>,
+    },
+    /// Saves a copy of the active game to another file
     Save {
         /// The filename to save the game to
         filename: String,
     },
-    /// Loads the game state
+    /// Loads a game from a file into the active save
     Load {
         /// The filename to load the game from
         filename: String,
@@ -29,37 +47,132 @@ pub enum Commands {
         from: String,
         #[clap(long)]
         to: String,
+        /// The plot to act on, by "X,Y" coordinate or name (defaults to the home plot)
+        #[clap(long)]
+        plot: Option<String>,
     },
-    /// Plants a seed
+    /// Plants a seed on each targeted tile
     Plant {
-        x: u32,
-        y: u32,
-        #[clap(long)]
-        seed: String,
+        #[clap(flatten)]
+        target: TargetArgs,
+        /// The species to plant
+        #[clap(long, required_unless_present = "seed_lot")]
+        seed: Option<String>,
+        /// Plants from a collected seed lot instead of a species
+        #[clap(long, conflicts_with = "seed")]
+        seed_lot: Option<u32>,
     },
-    /// Waters a tile
+    /// Waters each targeted tile
     Water {
-        x: u32,
-        y: u32,
+        #[clap(flatten)]
+        target: TargetArgs,
     },
-    /// Fertilizes a tile
+    /// Fertilizes each targeted tile
     Fertilize {
-        x: u32,
-        y: u32,
+        #[clap(flatten)]
+        target: TargetArgs,
         #[clap(long)]
         npk_mix: String,
     },
-    /// Harvests a mature plant
+    /// Harvests each targeted fruiting plant
     Harvest {
-        x: u32,
-        y: u32,
+        #[clap(flatten)]
+        target: TargetArgs,
+        /// Keeps part of each plant's yield back as a seed lot
+        #[clap(long)]
+        collect_seeds: bool,
+    },
+    /// Cross-pollinates two flowering or fruiting plants into a seed lot;
+    /// crossing two species creates a new hybrid species
+    Breed {
+        /// The seed parent, as "X,Y"
+        #[clap(value_parser = parse_tile)]
+        mother: (u32, u32),
+        /// The pollen parent, as "X,Y"
+        #[clap(value_parser = parse_tile)]
+        father: (u32, u32),
+        /// The plot both parents grow on (defaults to the home plot)
+        #[clap(long)]
+        plot: Option<String>,
+    },
+    /// Lists your bought seed and the seed lots you have collected or bred
+    Seeds,
+    /// Shows the family tree of a plant or seed lot, with the traits of
+    /// every ancestor
+    Lineage {
+        /// The plant's or seed lot's id, as shown by `inspect` and `seeds`
+        id: u32,
+    },
+    /// Treats each targeted plant against pests or disease
+    Treat {
+        /// What to apply; each works against particular pests and diseases
+        #[clap(value_enum)]
+        treatment: Treatment,
+        #[clap(flatten)]
+        target: TargetArgs,
     },
-    /// Applies pesticide to a tile
+    /// Deprecated: use `treat neem-oil`, the broad-spectrum spray that replaced pesticide
+    #[clap(hide = true)]
     Pesticide {
-        x: u32,
-        y: u32,
+        #[clap(flatten)]
+        target: TargetArgs,
     },
+    /// Shows soil, plant health and nutrient deficiencies for each targeted tile
+    Inspect {
+        #[clap(flatten)]
+        target: TargetArgs,
+    },
+    /// Hoes the weeds out of each targeted tile
+    #[clap(alias = "hoe")]
+    Weed {
+        #[clap(flatten)]
+        target: TargetArgs,
+        /// Also lays mulch to suppress regrowth
+        #[clap(long)]
+        mulch: bool,
+    },
+    /// Clears dead and visibly diseased plants from each targeted tile
+    Clear {
+        #[clap(flatten)]
+        target: TargetArgs,
+    },
+    /// Works on the soil itself
+    Soil(SoilCommand),
     Market(MarketCommand),
+    /// Lists and describes the species that can be planted
+    Species(SpeciesCommand),
+    /// Buys and lists plots of land
+    Land(LandCommand),
+    /// Lists recent transactions with profit and loss by crop and by period
+    Ledger {
+        /// The length of each profit and loss period, in ticks
+        #[clap(long, default_value_t = 30)]
+        period: u64,
+        /// How many of the most recent transactions to list
+        #[clap(long, default_value_t = 20)]
+        recent: usize,
+    },
+    /// Shows the transaction fee and land tax, changing any that are given
+    Fees {
+        /// Share of each trade's value charged as a fee, e.g. 0.02
+        #[clap(long)]
+        transaction_rate: Option<f32>,
+        /// Land tax per tile owned, in dollars
+        #[clap(long)]
+        land_tax: Option<f32>,
+        /// Ticks between land tax bills; 0 turns the tax off
+        #[clap(long)]
+        tax_period: Option<u64>,
+    },
+    /// Runs the simulation forward and saves the result
+    Advance {
+        /// The number of ticks to run
+        #[clap(long, default_value_t = 1)]
+        ticks: u64,
+        /// Forces the weather for every tick instead of rolling it
+        #[clap(long, value_enum)]
+        weather: Option<Weather>,
+    },
     Forecast {
         /// The number of ticks to forecast
         ticks: u64,
@@ -85,7 +198,216 @@ pub enum MarketCommands {
         quantity: u32,
     },
     /// Shows market prices
-    View,
+    View {
+        /// Only show produce, seeds or supplies
+        #[clap(long, value_enum)]
+        kind: Option<ItemKind>,
+    },
+    /// Places a standing order that trades once the price meets a trigger and
+    /// is checked every tick, e.g. `market order sell Sunpetal 20 --at-or-above 12.5 --expires 50`
+    Order {
+        #[clap(value_enum)]
+        side: Side,
+        item: String,
+        quantity: u32,
+        /// Trade once the price is at or above this
+        #[clap(long, required_unless_present = "at_or_below", conflicts_with = "at_or_below")]
+        at_or_above: Option<f32>,
+        /// Trade once the price is at or below this
+        #[clap(long)]
+        at_or_below: Option<f32>,
+        /// Drop the order if it has not filled within this many ticks
+        #[clap(long)]
+        expires: Option<u64>,
+    },
+    /// Lists your standing orders
+    Orders,
+    /// Cancels a standing order
+    Cancel {
+        id: u32,
+    },
+    /// Charts an item's recent prices with their minimum, maximum and mean
+    History {
+        item: String,
+        /// How many of the most recent ticks to show
+        #[clap(long, default_value_t = 30)]
+        ticks: usize,
+    },
+}
+
+#[derive(Parser, Debug)]
+pub struct SpeciesCommand {
+    #[clap(subcommand)]
+    pub command: SpeciesCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum SpeciesCommands {
+    /// Lists every species, catalogued and bred
+    List {
+        /// Only list species of this category
+        #[clap(long, value_enum)]
+        category: Option<Category>,
+    },
+    /// Shows every stat of one species
+    Show {
+        name: String,
+    },
+}
+
+/// Which tiles a tile command acts on: a single `X Y`, a range such as
+/// `3..7,2..5`, a whole row or column, or every tile, optionally narrowed
+/// by `--where` filters.
+#[derive(clap::Args, Debug)]
+pub struct TargetArgs {
+    /// Column index, range (`3..7`, `3..=6`) or `*`; may also be a full `X,Y` target
+    pub x: Option<String>,
+    /// Row index, range or `*`
+    pub y: Option<String>,
+    /// Targets every tile in the plot
+    #[clap(long, conflicts_with_all = ["x", "row", "col"])]
+    pub all: bool,
+    /// Targets a whole row
+    #[clap(long, conflicts_with = "x")]
+    pub row: Option<u32>,
+    /// Targets a whole column
+    #[clap(long, conflicts_with = "x")]
+    pub col: Option<u32>,
+    /// Only acts on tiles matching a filter such as `stage=fruiting` or `moisture<0.3`; repeatable
+    #[clap(long = "where")]
+    pub filters: Vec<String>,
+    /// The plot to act on, by "X,Y" coordinate or name (defaults to the home plot)
+    #[clap(long)]
+    pub plot: Option<String>,
+}
+
+impl TargetArgs {
+    pub fn selection(&self) -> Result<TileSelection, String> {
+        let filters = self
+            .filters
+            .iter()
+            .map(|filter| TileFilter::parse(filter))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (xs, ys) = match (&self.x, &self.y) {
+            (Some(x), Some(y)) => (AxisRange::parse(x)?, AxisRange::parse(y)?),
+            (Some(target), None) => {
+                let (x, y) = target
+                    .split_once(',')
+                    .ok_or_else(|| format!("'{}' needs both a column and a row, like '3..7,2..5'", target))?;
+                (AxisRange::parse(x)?, AxisRange::parse(y)?)
+            }
+            _ => {
+                if !self.all && self.row.is_none() && self.col.is_none() && filters.is_empty() {
+                    return Err("Specify a tile (X Y), a range, --row, --col, --all or --where.".to_string());
+                }
+                (
+                    self.col.map_or(AxisRange::ALL, AxisRange::single),
+                    self.row.map_or(AxisRange::ALL, AxisRange::single),
+                )
+            }
+        };
+        Ok(TileSelection { xs, ys, filters })
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct SoilCommand {
+    #[clap(subcommand)]
+    pub command: SoilCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum SoilCommands {
+    /// Works sand, compost or clay into each targeted tile to shift its soil type over time
+    Amend {
+        #[clap(flatten)]
+        target: TargetArgs,
+        /// The material to work in
+        #[clap(long, value_enum)]
+        with: AmendmentKind,
+    },
+    /// Spreads lime on each targeted tile to gradually raise its pH
+    Lime {
+        #[clap(flatten)]
+        target: TargetArgs,
+    },
+    /// Spreads sulfur on each targeted tile to gradually lower its pH
+    Sulfur {
+        #[clap(flatten)]
+        target: TargetArgs,
+    },
+}
+
+#[derive(Parser, Debug)]
+pub struct LandCommand {
+    #[clap(subcommand)]
+    pub command: LandCommands,
+}
+
+#[derive(Parser, Debug)]
+pub enum LandCommands {
+    /// Buys a new plot of land
+    Buy {
+        /// World coordinate of the plot's top-left corner, as "X,Y"
+        #[clap(long, value_parser = parse_coordinate)]
+        at: (i32, i32),
+        /// Plot size, as "WxH"
+        #[clap(long, value_parser = parse_size)]
+        size: (u32, u32),
+        /// An optional name to select the plot by
+        #[clap(long)]
+        name: Option<String>,
+    },
+    /// Lists owned plots
+    List,
+}
+
+fn parse_coordinate(value: &str) -> Result<(i32, i32), String> {
+    crate::garden::parse_coordinate(value).ok_or_else(|| format!("expected X,Y but got '{}'", value))
+}
+
+fn parse_tile(value: &str) -> Result<(u32, u32), String> {
+    value
+        .split_once(',')
+        .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)))
+        .ok_or_else(|| format!("expected X,Y tile indices but got '{}'", value))
+}
+
+fn parse_size(value: &str) -> Result<(u32, u32), String> {
+    let (width, height): (u32, u32) = value
+        .split_once('x')
+        .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)))
+        .ok_or_else(|| format!("expected WxH but got '{}'", value))?;
+    if width > MAX_PLOT_SIDE || height > MAX_PLOT_SIDE {
+        return Err(format!("a plot can be at most {}x{}", MAX_PLOT_SIDE, MAX_PLOT_SIDE));
+    }
+    Ok((width, height))
+}
+
+impl Commands {
+    /// Whether the command changes the game state and so must be written back to the save.
+    pub fn mutates_state(&self) -> bool {
+        match self {
+            Commands::Save { .. }
+            | Commands::View { .. }
+            | Commands::Forecast { .. }
+            | Commands::Inspect { .. }
+            | Commands::Seeds
+            | Commands::Lineage { .. }
+            | Commands::Ledger { .. }
+            | Commands::Species(_) => false,
+            Commands::Fees { transaction_rate, land_tax, tax_period } => {
+                transaction_rate.is_some() || land_tax.is_some() || tax_period.is_some()
+            }
+            Commands::Market(market_command) => !matches!(
+                market_command.command,
+                MarketCommands::View { .. } | MarketCommands::Orders | MarketCommands::History { .. }
+            ),
+            Commands::Land(land_command) => !matches!(land_command.command, LandCommands::List),
+            _ => true,
+        }
+    }
 }
 
 pub fn parse_args() -> Args {
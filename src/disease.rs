@@ -0,0 +1,140 @@
+// src/disease.rs
+
+use crate::garden::{Soil, FIELD_CAPACITY};
+use crate::weather::Weather;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum DiseaseType {
+    Blight,
+    PowderyMildew,
+    RootRot,
+}
+
+/// An infection on a plant. It spreads unseen while `incubation` counts
+/// down, then shows symptoms and starts doing damage.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Disease {
+    pub disease_type: DiseaseType,
+    /// Ticks left before symptoms appear.
+    pub incubation: u32,
+    /// How far the disease has progressed, from 0.0 to 1.0.
+    pub severity: f32,
+}
+
+/// A soil-borne disease lingering in a tile after its host is gone.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Contamination {
+    pub disease_type: DiseaseType,
+    pub ticks_remaining: u32,
+}
+
+/// How a disease type behaves once it has infected a plant.
+pub struct DiseaseProfile {
+    /// Ticks from infection to visible symptoms.
+    pub incubation_ticks: u32,
+    /// Severity gained per tick once symptomatic.
+    pub progression: f32,
+    /// Plant health lost per tick at full severity.
+    pub health_damage: f32,
+    /// Chance per tick, at full severity, of infecting a neighbouring plant.
+    pub spread_chance: f32,
+    /// Ticks a tile stays contaminated after hosting the disease; 0 if it
+    /// does not survive in the soil.
+    pub contamination_ticks: u32,
+    /// Whether fungicide cures it; otherwise the plant has to be removed.
+    pub treatable: bool,
+}
+
+/// Chance per tick that a contaminated tile infects a new plant.
+pub const CONTAMINATION_INFECTION_CHANCE: f32 = 0.2;
+
+impl DiseaseType {
+    pub const ALL: [DiseaseType; 3] = [DiseaseType::Blight, DiseaseType::PowderyMildew, DiseaseType::RootRot];
+
+    /// Blight is fast, deadly and lingers in the soil; mildew is slow and
+    /// mild but drifts between plants; root rot spreads only through the
+    /// soil and cannot be sprayed away.
+    pub fn profile(&self) -> DiseaseProfile {
+        match self {
+            DiseaseType::Blight => DiseaseProfile {
+                incubation_ticks: 3,
+                progression: 0.1,
+                health_damage: 0.15,
+                spread_chance: 0.4,
+                contamination_ticks: 30,
+                treatable: true,
+            },
+            DiseaseType::PowderyMildew => DiseaseProfile {
+                incubation_ticks: 2,
+                progression: 0.05,
+                health_damage: 0.05,
+                spread_chance: 0.5,
+                contamination_ticks: 0,
+                treatable: true,
+            },
+            DiseaseType::RootRot => DiseaseProfile {
+                incubation_ticks: 4,
+                progression: 0.08,
+                health_damage: 0.1,
+                spread_chance: 0.0,
+                contamination_ticks: 40,
+                treatable: false,
+            },
+        }
+    }
+
+    /// Chance per tick that this disease takes hold on an uninfected plant,
+    /// before the plant's resistance. `crowding` is the share of the eight
+    /// surrounding tiles that hold plants. Blight needs wet leaves, mildew
+    /// thrives in still, overcast air between packed plants, and root rot
+    /// needs waterlogged soil.
+    pub fn infection_chance(&self, weather: Weather, soil: &Soil, crowding: f32) -> f32 {
+        match self {
+            DiseaseType::Blight => {
+                let wet = match weather {
+                    Weather::Rainy => 1.0,
+                    Weather::Cloudy => 0.3,
+                    _ => 0.0,
+                };
+                0.02 * wet * (1.0 + soil.soil_moisture) * (1.0 + crowding)
+            }
+            DiseaseType::PowderyMildew => {
+                let still = match weather {
+                    Weather::Cloudy => 1.0,
+                    Weather::Sunny => 0.3,
+                    Weather::Rainy | Weather::Heatwave => 0.1,
+                };
+                0.01 * still * (1.0 + 4.0 * crowding)
+            }
+            DiseaseType::RootRot => {
+                let waterlogging = (soil.soil_moisture - FIELD_CAPACITY).max(0.0) / (1.0 - FIELD_CAPACITY);
+                0.1 * waterlogging * (1.0 - soil.soil_type.properties().drainage)
+            }
+        }
+    }
+
+    /// What the disease looks like once it has shown itself.
+    pub fn symptoms(&self) -> &'static str {
+        match self {
+            DiseaseType::Blight => "dark lesions on leaves and stems",
+            DiseaseType::PowderyMildew => "white powdery coating on leaves",
+            DiseaseType::RootRot => "wilting despite wet soil",
+        }
+    }
+}
+
+impl Disease {
+    pub fn new(disease_type: DiseaseType) -> Disease {
+        Disease {
+            disease_type,
+            incubation: disease_type.profile().incubation_ticks,
+            severity: 0.1,
+        }
+    }
+
+    /// Whether the disease has shown symptoms yet.
+    pub fn is_visible(&self) -> bool {
+        self.incubation == 0
+    }
+}
@@ -1,46 +1,239 @@
 // src/economy.rs
 
+use crate::breeding::{Hybrid, HYBRID_RARITY};
+use crate::catalogue::{self, Rarity};
+use crate::money::Money;
+use crate::plant::{Plant, PlantGenetics};
+use crate::treatment::Treatment;
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, VecDeque};
 use rand::Rng;
 
+/// What sort of thing a market item is: produce can only be sold, while
+/// seeds and supplies can only be bought.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ItemKind {
+    Produce,
+    Seed,
+    Supply,
+}
+
+impl ItemKind {
+    /// How strongly the price of this kind of item answers an imbalance of
+    /// supply and demand: produce is a glut-or-famine commodity, while seed
+    /// and supplies are sold from stock at steadier prices.
+    pub fn elasticity(&self) -> f32 {
+        match self {
+            ItemKind::Produce => 1.0,
+            ItemKind::Seed => 0.5,
+            ItemKind::Supply => 0.3,
+        }
+    }
+}
+
+/// One good the market trades. Its price is `base_price` scaled by the ratio
+/// of demand to supply raised to `elasticity`; both levels sit at 1.0 in a
+/// quiet market, are pushed about by trades and drift back to 1.0 each tick.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Listing {
+    pub kind: ItemKind,
+    /// The current price of one unit; kept in step by `reprice`.
+    pub price: f32,
+    /// The price the market settles back to. Zero in saves from before the
+    /// price model, until `Market::restock` fills it in.
+    #[serde(default)]
+    pub base_price: f32,
+    #[serde(default = "neutral_level")]
+    pub demand: f32,
+    #[serde(default = "neutral_level")]
+    pub supply: f32,
+    #[serde(default = "neutral_level")]
+    pub elasticity: f32,
+    /// The price at the end of each of the last `PRICE_HISTORY_LENGTH` ticks, oldest first.
+    #[serde(default)]
+    pub history: VecDeque<f32>,
+}
+
+fn neutral_level() -> f32 {
+    1.0
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Market {
-    pub items: HashMap<String, f32>,
-    pub supply_demand: HashMap<String, f32>,
+    /// Everything the market trades, by item name. Saves from before the
+    /// catalogue have none; `load_game` restocks them.
+    #[serde(default)]
+    pub listings: BTreeMap<String, Listing>,
+}
+
+/// Units of an item that, sold at once, double its supply level; the same
+/// number bought doubles its demand.
+pub const MARKET_DEPTH: f32 = 50.0;
+/// Share of the gap back to a neutral 1.0 that supply and demand close each tick.
+pub const MEAN_REVERSION: f32 = 0.05;
+/// Largest random shift in demand per tick.
+pub const DEMAND_VOLATILITY: f32 = 0.02;
+/// The price never falls below this share of the base price...
+pub const PRICE_FLOOR_SHARE: f32 = 0.1;
+/// ...nor rises above this multiple of it.
+pub const PRICE_CEILING_SHARE: f32 = 5.0;
+/// Ticks of price history kept per item.
+pub const PRICE_HISTORY_LENGTH: usize = 100;
+
+impl Listing {
+    pub fn new(kind: ItemKind, base_price: f32) -> Listing {
+        Listing {
+            kind,
+            price: base_price,
+            base_price,
+            demand: 1.0,
+            supply: 1.0,
+            elasticity: kind.elasticity(),
+            history: VecDeque::new(),
+        }
+    }
+
+    /// The price once `sold` more units have been sold and `bought` more bought.
+    fn price_after(&self, sold: u32, bought: u32) -> f32 {
+        let demand = self.demand + bought as f32 / MARKET_DEPTH;
+        let supply = self.supply + sold as f32 / MARKET_DEPTH;
+        let pressure = (demand / supply.max(f32::EPSILON)).powf(self.elasticity);
+        (self.base_price * pressure).clamp(self.base_price * PRICE_FLOOR_SHARE, self.base_price * PRICE_CEILING_SHARE)
+    }
+
+    /// Recomputes `price` from the base price and the current supply and demand.
+    pub fn reprice(&mut self) {
+        self.price = self.price_after(0, 0);
+    }
+
+    /// Sells `quantity` units into the market, each at the price left by the
+    /// one before, and returns the total proceeds.
+    fn absorb_sale(&mut self, quantity: u32) -> Money {
+        let proceeds = total_with_slippage(quantity, |unit| self.price_after(unit, 0));
+        self.supply += quantity as f32 / MARKET_DEPTH;
+        self.reprice();
+        proceeds
+    }
+
+    /// Buys `quantity` units out of the market, each dearer than the last,
+    /// and returns the total cost.
+    fn absorb_purchase(&mut self, quantity: u32) -> Money {
+        let cost = self.quote_purchase(quantity);
+        self.demand += quantity as f32 / MARKET_DEPTH;
+        self.reprice();
+        cost
+    }
+
+    /// What buying `quantity` units now would cost, slippage included.
+    pub fn quote_purchase(&self, quantity: u32) -> Money {
+        total_with_slippage(quantity, |unit| self.price_after(0, unit))
+    }
+}
+
+/// The sum of `unit_price` over the first `quantity` units, each rounded to
+/// the cent, for a price that only ever moves one way. Once it stops moving,
+/// pinned at the floor or the ceiling, the remaining units are counted at
+/// that price in one step.
+fn total_with_slippage(quantity: u32, unit_price: impl Fn(u32) -> f32) -> Money {
+    let Some(last_unit) = quantity.checked_sub(1) else { return Money::ZERO };
+    let last_price = unit_price(last_unit);
+    let mut total = Money::ZERO;
+    for unit in 0..quantity {
+        let price = unit_price(unit);
+        if price == last_price {
+            return total + Money::from_f32(last_price).times(quantity - unit);
+        }
+        total += Money::from_f32(price);
+    }
+    total
+}
+
+/// Price of one unit of produce per tick of growth time, before dividing by
+/// the mean yield and scaling by rarity.
+pub const PRODUCE_PRICE_PER_GROWTH_TICK: f32 = 2.0;
+/// Price of a seed as a share of the price of its produce.
+pub const SEED_PRICE_SHARE: f32 = 0.5;
+
+/// The market name of `species`' seeds; its produce trades under the species name.
+pub fn seed_item(species: &str) -> String {
+    format!("{} Seeds", species)
+}
+
+/// The species whose seeds trade as `item_name`, if it is a seed item.
+pub fn seed_species(item_name: &str) -> Option<&str> {
+    item_name.strip_suffix(" Seeds")
+}
+
+/// What one unit of produce from a plant with `genetics` should cost: slow
+/// growers and shy croppers are dearer, as are rarer species.
+pub fn produce_price(genetics: &PlantGenetics, rarity: Rarity) -> f32 {
+    let mean_yield = ((genetics.yield_range.0 + genetics.yield_range.1) as f32 / 2.0).max(1.0);
+    PRODUCE_PRICE_PER_GROWTH_TICK * genetics.growth_time as f32 / mean_yield * rarity.price_multiplier()
+}
+
+impl Market {
+    pub fn price(&self, item_name: &str) -> Option<f32> {
+        self.listings.get(item_name).map(|listing| listing.price)
+    }
+
+    /// Lists produce and seeds for `template`'s species unless they are
+    /// already traded, leaving existing prices alone.
+    pub fn list_species(&mut self, template: &Plant, rarity: Rarity) {
+        let price = produce_price(&template.genetics, rarity);
+        self.listings
+            .entry(template.species.clone())
+            .or_insert_with(|| Listing::new(ItemKind::Produce, price));
+        self.listings
+            .entry(seed_item(&template.species))
+            .or_insert_with(|| Listing::new(ItemKind::Seed, price * SEED_PRICE_SHARE));
+    }
+
+    /// Lists every catalogued and bred species and every treatment that is
+    /// not traded yet.
+    pub fn restock(&mut self, hybrids: &BTreeMap<String, Hybrid>) {
+        for species in catalogue::catalogue().iter() {
+            self.list_species(&species.template, species.rarity);
+        }
+        for hybrid in hybrids.values() {
+            self.list_species(&hybrid.template, HYBRID_RARITY);
+        }
+        for treatment in Treatment::value_variants() {
+            self.listings
+                .entry(treatment.item_name().to_string())
+                .or_insert_with(|| Listing::new(ItemKind::Supply, treatment.profile().cost));
+        }
+        for listing in self.listings.values_mut().filter(|listing| listing.base_price <= 0.0) {
+            listing.base_price = listing.price;
+            listing.elasticity = listing.kind.elasticity();
+        }
+    }
 }
 
 impl Default for Market {
     fn default() -> Self {
-        let mut items = HashMap::new();
-        items.insert("tomato".to_string(), 10.0);
-        items.insert("potato".to_string(), 5.0);
-        items.insert("corn".to_string(), 15.0);
-
-        Market {
-            items,
-            supply_demand: HashMap::new(),
-        }
+        let mut market = Market { listings: BTreeMap::new() };
+        market.restock(&BTreeMap::new());
+        market
     }
 }
 
+/// Sells `quantity` of the produce `item_name` out of `inventory`, returning
+/// the proceeds before fees. Each unit sold depresses the price of the next.
 pub fn sell_item(
-    inventory: &mut HashMap<String, u32>,
-    wallet: &mut f32,
+    inventory: &mut BTreeMap<String, u32>,
     market: &mut Market,
     item_name: &str,
     quantity: u32,
-) -> Result<(), &'static str> {
-    if let Some(price) = market.items.get(item_name) {
+) -> Result<Money, &'static str> {
+    if let Some(listing) = market.listings.get_mut(item_name) {
+        if listing.kind != ItemKind::Produce {
+            return Err("Only produce can be sold.");
+        }
         if let Some(available_quantity) = inventory.get_mut(item_name) {
             if *available_quantity >= quantity {
                 *available_quantity -= quantity;
-                *wallet += price * quantity as f32;
-
-                let supply_demand_effect = market.supply_demand.entry(item_name.to_string()).or_insert(1.0);
-                *supply_demand_effect -= (quantity as f32) / 100.0;
-
-                Ok(())
+                Ok(listing.absorb_sale(quantity))
             } else {
                 Err("Not enough items to sell.")
             }
@@ -52,56 +245,107 @@ pub fn sell_item(
     }
 }
 
-pub fn update_market_prices(market: &mut Market) {
-    for (item, price) in market.items.iter_mut() {
-        let change = rand::thread_rng().gen_range(-0.05..0.05);
-        let supply_demand_effect = market.supply_demand.get(item).cloned().unwrap_or(1.0);
-        *price *= 1.0 + change + supply_demand_effect - 1.0;
-
-        if *price < 0.1 {
-            *price = 0.1;
+/// Moves every market a tick on: supply and demand drift back towards
+/// neutral, demand takes a small random step, and prices follow. Prices are
+/// recomputed from scratch each tick, so nothing compounds.
+pub fn update_market_prices(market: &mut Market, rng: &mut impl Rng) {
+    for listing in market.listings.values_mut() {
+        listing.supply += (1.0 - listing.supply) * MEAN_REVERSION;
+        listing.demand += (1.0 - listing.demand) * MEAN_REVERSION;
+        listing.demand = (listing.demand + rng.gen_range(-DEMAND_VOLATILITY..=DEMAND_VOLATILITY)).max(0.0);
+        listing.reprice();
+        listing.history.push_back(listing.price);
+        if listing.history.len() > PRICE_HISTORY_LENGTH {
+            listing.history.pop_front();
         }
+    }
+}
 
-        if let Some(supply_demand_effect) = market.supply_demand.get_mut(item) {
-            *supply_demand_effect += 0.005;
-            if *supply_demand_effect > 1.0 {
-                *supply_demand_effect = 1.0;
-            }
-        }
+/// What buying `quantity` of `item_name` would cost before fees, slippage included.
+pub fn quote_purchase(market: &Market, item_name: &str, quantity: u32) -> Result<Money, &'static str> {
+    let listing = market.listings.get(item_name).ok_or("Item not found in market.")?;
+    if listing.kind == ItemKind::Produce {
+        return Err("Produce can only be sold, not bought.");
     }
+    Ok(listing.quote_purchase(quantity))
 }
 
+/// Buys `quantity` of `item_name`, returning the cost before fees; check it
+/// is affordable with `quote_purchase` first. Seeds go to `seed_inventory`
+/// under their species, ready to plant; supplies go to `inventory`. Each
+/// unit bought makes the next dearer.
 pub fn buy_item(
-    inventory: &mut HashMap<String, u32>,
-    wallet: &mut f32,
-    market: &Market,
+    inventory: &mut BTreeMap<String, u32>,
+    seed_inventory: &mut BTreeMap<String, u32>,
+    market: &mut Market,
     item_name: &str,
     quantity: u32,
-) -> Result<(), &'static str> {
-    if let Some(price) = market.items.get(item_name) {
-        let cost = price * quantity as f32;
-        if *wallet >= cost {
-            *wallet -= cost;
-            let entry = inventory.entry(item_name.to_string()).or_insert(0);
-            *entry += quantity;
-            Ok(())
-        } else {
-            Err("Not enough cash to buy.")
-        }
-    } else {
-        Err("Item not found in market.")
+) -> Result<Money, &'static str> {
+    let cost = quote_purchase(market, item_name, quantity)?;
+    let listing = market.listings.get_mut(item_name).ok_or("Item not found in market.")?;
+    listing.absorb_purchase(quantity);
+    let entry = match seed_species(item_name).filter(|_| listing.kind == ItemKind::Seed) {
+        Some(species) => seed_inventory.entry(species.to_string()).or_insert(0),
+        None => inventory.entry(item_name.to_string()).or_insert(0),
+    };
+    *entry += quantity;
+    Ok(cost)
+}
+
+/// The last `ticks` recorded prices of `item_name`, oldest first.
+pub fn recent_prices(market: &Market, item_name: &str, ticks: usize) -> Option<Vec<f32>> {
+    let history = &market.listings.get(item_name)?.history;
+    Some(history.iter().skip(history.len().saturating_sub(ticks)).copied().collect())
+}
+
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One character per price, from '▁' at the lowest to '█' at the highest.
+pub fn sparkline(prices: &[f32]) -> String {
+    let low = prices.iter().copied().fold(f32::INFINITY, f32::min);
+    let high = prices.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    prices
+        .iter()
+        .map(|price| {
+            let level = if high > low { (price - low) / (high - low) } else { 0.5 };
+            SPARK_LEVELS[(level * (SPARK_LEVELS.len() - 1) as f32).round() as usize]
+        })
+        .collect()
+}
+
+/// A sparkline of `item_name`'s last `ticks` prices with their minimum,
+/// maximum and mean, for `market history`.
+pub fn price_history_report(market: &Market, item_name: &str, ticks: usize) -> Result<String, String> {
+    let prices = recent_prices(market, item_name, ticks).ok_or_else(|| format!("'{}' is not traded", item_name))?;
+    if prices.is_empty() {
+        return Err(format!("No price history for '{}' yet; advance a tick first", item_name));
     }
+    Ok(format!(
+        "{}, last {} tick(s):\n{}\nnow {:.2} | {}",
+        item_name,
+        prices.len(),
+        sparkline(&prices),
+        prices[prices.len() - 1],
+        price_summary(&prices)
+    ))
 }
 
-pub fn get_market_price(_item: &str) -> f32 {
-    // Placeholder for market price logic
-    10.0
+/// The lowest, highest and mean of `prices`, e.g. "min 1.00 | max 5.00 | mean 2.67".
+pub fn price_summary(prices: &[f32]) -> String {
+    let min = prices.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = prices.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let mean = prices.iter().sum::<f32>() / prices.len().max(1) as f32;
+    format!("min {:.2} | max {:.2} | mean {:.2}", min, max, mean)
 }
 
-pub fn view_market(market: &Market) -> String {
-    let mut market_view = String::from("Item\t\tPrice\n");
-    for (item, price) in &market.items {
-        market_view.push_str(&format!("{}\t\t{}\n", item, price));
+/// The market's prices, optionally of one kind of item only.
+pub fn view_market(market: &Market, kind: Option<ItemKind>) -> String {
+    let mut market_view = String::from("Item\t\t\tKind\t\tPrice\n");
+    for (item, listing) in &market.listings {
+        if kind.is_some_and(|kind| kind != listing.kind) {
+            continue;
+        }
+        market_view.push_str(&format!("{}\t\t{:?}\t\t{:.2}\n", item, listing.kind, listing.price));
     }
     market_view
 }
@@ -1,60 +1,362 @@
 // src/engine.rs
 
-use crate::garden::{create_grid, MainGameState, Plot};
+use crate::garden::{create_grid, Amendment, AmendmentKind, MainGameState, Plot, FIELD_CAPACITY, HOME_PLOT, MAX_PLOT_SIDE};
 use crate::plant;
-use crate::weather::Weather;
-use std::collections::HashMap;
-use crate::economy::Market;
+use crate::light;
+use crate::weather::{Season, Weather};
+use std::collections::BTreeMap;
+use crate::economy::{ItemKind, Market};
+use crate::ledger::{FeeSchedule, Ledger, TransactionKind};
+use crate::money::Money;
+use crate::rng;
+use crate::targeting::TileSelection;
 
 pub fn new_game() -> MainGameState {
-    let mut plots = HashMap::new();
+    new_game_with_seed(rng::random_seed())
+}
+
+pub fn new_game_with_seed(seed: u64) -> MainGameState {
+    let mut rng = rng::seeded(seed);
+    let mut plots = BTreeMap::new();
     let initial_plot = Plot {
-        x: 0,
-        y: 0,
-        grid: create_grid(10, 10),
+        x: HOME_PLOT.0,
+        y: HOME_PLOT.1,
+        name: Some("home".to_string()),
+        grid: create_grid(10, 10, &mut rng),
     };
-    plots.insert((0, 0), initial_plot);
+    plots.insert(HOME_PLOT, initial_plot);
 
 
     MainGameState {
         plots,
         tick_counter: 0,
-        inventory: HashMap::new(),
-        wallet: 100.0,
+        inventory: BTreeMap::new(),
+        wallet: Money::from_cents(10_000),
         market: Market::default(),
         current_weather: Weather::Sunny,
         events: Vec::new(),
+        treatment_resistance: BTreeMap::new(),
+        seed_inventory: BTreeMap::new(),
+        seed_lots: BTreeMap::new(),
+        hybrids: BTreeMap::new(),
+        pedigree: BTreeMap::new(),
+        next_id: 1,
+        orders: BTreeMap::new(),
+        next_order_id: 1,
+        ledger: Ledger::default(),
+        fees: FeeSchedule::default(),
+        catalogue: None,
+        rng,
     }
 }
 
-pub fn plant_seed(game_state: &mut MainGameState, x: u32, y: u32, seed: &str) {
-    if let Some(plot) = game_state.plots.get_mut(&(0, 0)) {
+/// Plants one bought `seed` at (`x`, `y`) on `plot`. Returns whether anything was planted.
+pub fn plant_seed(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32, seed: &str) -> bool {
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
         if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
             if tile.plant.is_none() {
-                let new_plant = plant::create_plant(seed);
+                let Some(stock) = game_state.seed_inventory.get_mut(seed).filter(|stock| **stock > 0) else {
+                    println!("{}", seeds::no_seeds(seed));
+                    return false;
+                };
+                let mut new_plant = match plant::create_plant(seed, &game_state.hybrids, &mut game_state.rng) {
+                    Ok(new_plant) => new_plant,
+                    Err(e) => {
+                        println!("{}", e);
+                        return false;
+                    }
+                };
+                *stock -= 1;
+                new_plant.id = game_state.next_id;
+                game_state.next_id += 1;
+                game_state.pedigree.insert(new_plant.id, Ancestor::of_plant(&new_plant, game_state.tick_counter));
                 tile.plant = Some(new_plant);
                 println!("Planted a {} at ({}, {})", seed, x, y);
+                return true;
             } else {
                 println!("There is already a plant at ({}, {})", x, y);
             }
         } else {
             println!("Invalid coordinates: ({}, {})", x, y);
         }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
+    }
+    false
+}
+
+/// Plants one seed from seed lot `lot_id` at (`x`, `y`) on `plot`.
+pub fn plant_seed_lot(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32, lot_id: u32) -> bool {
+    let Some(lot) = game_state.seed_lots.get_mut(&lot_id) else {
+        println!("There is no seed lot {}", lot_id);
+        return false;
+    };
+    if lot.quantity == 0 {
+        println!("Seed lot {} is empty.", lot_id);
+        return false;
+    }
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
+        if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
+            if tile.plant.is_none() {
+                let (genome, mutations) = lot.roll_seed(&mut game_state.rng);
+                let Some(mut new_plant) = plant::create_plant_from_genome(&lot.species, &game_state.hybrids, genome) else {
+                    println!("Unknown species '{}' in seed lot {}", lot.species, lot_id);
+                    return false;
+                };
+                new_plant.id = game_state.next_id;
+                new_plant.parents = vec![lot_id];
+                new_plant.generation = lot.generation;
+                game_state.next_id += 1;
+                game_state.pedigree.insert(new_plant.id, Ancestor::of_plant(&new_plant, game_state.tick_counter));
+                lot.quantity -= 1;
+                tile.plant = Some(new_plant);
+                println!(
+                    "Planted a {} from seed lot {} at ({}, {}) ({} mutation(s))",
+                    lot.species, lot_id, x, y, mutations
+                );
+                return true;
+            } else {
+                println!("There is already a plant at ({}, {})", x, y);
+            }
+        } else {
+            println!("Invalid coordinates: ({}, {})", x, y);
+        }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
+    }
+    false
+}
+
+/// Cross-pollinates the plants at `mother` and `father` on `plot` and stores
+/// the seeds as a new seed lot, returning its id. Both parents must be in
+/// flower or fruit and within `POLLINATION_RANGE` of each other. Crossing two
+/// species creates a hybrid species, which is registered and listed on the
+/// market the first time that pair is crossed.
+pub fn breed(
+    game_state: &mut MainGameState,
+    plot: (i32, i32),
+    mother: (u32, u32),
+    father: (u32, u32),
+) -> Result<u32, String> {
+    if mother == father {
+        return Err("A plant cannot be crossed with itself; collect its seeds at harvest instead".to_string());
+    }
+    let plot = game_state
+        .plots
+        .get(&plot)
+        .ok_or_else(|| format!("No plot at ({}, {})", plot.0, plot.1))?;
+    let parent = |(x, y): (u32, u32)| {
+        let plant = plot
+            .grid
+            .tiles
+            .get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .ok_or_else(|| format!("Invalid coordinates: ({}, {})", x, y))?
+            .plant
+            .as_ref()
+            .ok_or_else(|| format!("There is no plant at ({}, {})", x, y))?;
+        match plant.life_cycle_stage {
+            plant::LifeCycleStage::Mature | plant::LifeCycleStage::Fruiting => Ok(plant),
+            ref stage => Err(format!("The {} at ({}, {}) is {:?}, not flowering or fruiting", plant.species, x, y, stage)),
+        }
+    };
+    let (mother_plant, father_plant) = (parent(mother)?, parent(father)?);
+    if mother.0.abs_diff(father.0).max(mother.1.abs_diff(father.1)) > POLLINATION_RANGE {
+        return Err(format!("The plants are more than {} tiles apart", POLLINATION_RANGE));
+    }
+
+    let stability = (mother_plant.genetics.genetic_stability + father_plant.genetics.genetic_stability) / 2.0;
+    let (mother_id, father_id) = (mother_plant.id, father_plant.id);
+    let (mother_genome, father_genome) = (mother_plant.genome.clone(), father_plant.genome.clone());
+    let generation = mother_plant.generation.max(father_plant.generation) + 1;
+    let lot_genetics = mother_plant.genetics.clone();
+
+    let species = if mother_plant.species == father_plant.species {
+        mother_plant.species.clone()
+    } else if let Some(hybrid) = find_hybrid(&game_state.hybrids, &mother_plant.species, &father_plant.species) {
+        hybrid.name.clone()
+    } else {
+        let name = hybrid_name(&mother_plant.species, &father_plant.species, |name| {
+            game_state.hybrids.contains_key(name) || plant::species_template(&game_state.hybrids, name).is_some()
+        });
+        // Bought seed of the new hybrid grows from one representative cross
+        let rng = &mut game_state.rng;
+        let mut genome = Genome::cross(&mother_plant.genome, &father_plant.genome, rng);
+        genome.mutate(stability, rng);
+        let template = plant::Plant {
+            species: name.clone(),
+            genetics: genome.express(&mother_plant.genetics),
+            life_cycle_stage: plant::LifeCycleStage::Seed,
+            age: 0,
+            maturity_age: (mother_plant.maturity_age + father_plant.maturity_age) / 2,
+            wither_time: (mother_plant.wither_time + father_plant.wither_time) / 2,
+            growth_progress: 0.0,
+            health: 1.0,
+            deficiency: plant::Deficiency::default(),
+            pollination: 0.0,
+            genome,
+            id: 0,
+            parents: Vec::new(),
+            generation: 0,
+        };
+        game_state.market.list_species(&template, HYBRID_RARITY);
+        println!(
+            "A new hybrid, {}, has been bred from {} and {}!",
+            name, mother_plant.species, father_plant.species
+        );
+        game_state.hybrids.insert(
+            name.clone(),
+            Hybrid {
+                name: name.clone(),
+                parents: (mother_plant.species.clone(), father_plant.species.clone()),
+                bred_at: game_state.tick_counter,
+                template,
+                category: catalogue::catalogue()
+                    .get(&mother_plant.species)
+                    .map(|species| species.category)
+                    .or_else(|| game_state.hybrids.get(&mother_plant.species).map(|hybrid| hybrid.category))
+                    .unwrap_or_default(),
+            },
+        );
+        name
+    };
+
+    let id = game_state.next_id;
+    game_state.next_id += 1;
+    let lot = SeedLot {
+        id,
+        species,
+        mother: mother_genome,
+        father: father_genome,
+        stability,
+        quantity: SEEDS_PER_CROSS,
+        collected_at: game_state.tick_counter,
+        parents: vec![mother_id, father_id],
+        generation,
+    };
+    game_state.pedigree.insert(id, Ancestor::of_seed_lot(&lot, lot_genetics));
+    game_state.seed_lots.insert(id, lot);
+    Ok(id)
+}
+
+/// What watering one tile costs.
+pub const WATER_COST: Money = Money::from_cents(10);
+/// What fertilizing costs per unit of N, P and K added.
+pub const FERTILIZER_COST_PER_UNIT: Money = Money::from_cents(200);
+
+pub fn water_tile(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32) -> bool {
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
+        if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
+            if tile.soil.soil_moisture >= 1.0 {
+                println!("Tile ({}, {}) is already saturated.", x, y);
+                return false;
+            }
+            if game_state.wallet < WATER_COST {
+                println!("Not enough cash to water: it costs ${}.", WATER_COST);
+                return false;
+            }
+            let tick = game_state.tick_counter;
+            game_state.ledger.pay(&mut game_state.wallet, tick, TransactionKind::Expense, "Watering", 1, WATER_COST);
+            tile.soil.soil_moisture += 0.2 * tile.soil.soil_type.properties().absorption_factor();
+            tile.soil.soil_moisture = tile.soil.soil_moisture.clamp(0.0, 1.0);
+            println!("Watered tile ({}, {}). New moisture: {}", x, y, tile.soil.soil_moisture);
+            return true;
+        } else {
+            println!("Invalid coordinates: ({}, {})", x, y);
+        }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
     }
+    false
+}
+
+pub fn fertilize_tile(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32, npk: (f32, f32, f32)) -> bool {
+    if [npk.0, npk.1, npk.2].iter().any(|amount| !amount.is_finite() || *amount < 0.0) {
+        println!("Fertilizer amounts must be zero or more.");
+        return false;
+    }
+    let cost = FERTILIZER_COST_PER_UNIT.scale(npk.0 + npk.1 + npk.2);
+    if game_state.wallet < cost {
+        println!("Not enough cash to fertilize: it costs ${}.", cost);
+        return false;
+    }
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
+        if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
+            let retained = tile.soil.soil_type.properties().fertilizer_factor();
+            tile.soil.soil_nutrients.nitrogen += npk.0 * retained;
+            tile.soil.soil_nutrients.phosphorus += npk.1 * retained;
+            tile.soil.soil_nutrients.potassium += npk.2 * retained;
+
+            // Clamp nutrient values
+            tile.soil.soil_nutrients.nitrogen = tile.soil.soil_nutrients.nitrogen.clamp(0.0, 1.0);
+            tile.soil.soil_nutrients.phosphorus = tile.soil.soil_nutrients.phosphorus.clamp(0.0, 1.0);
+            tile.soil.soil_nutrients.potassium = tile.soil.soil_nutrients.potassium.clamp(0.0, 1.0);
+
+            let tick = game_state.tick_counter;
+            game_state.ledger.pay(&mut game_state.wallet, tick, TransactionKind::Expense, "Fertilizer", 1, cost);
+            println!("Fertilized tile ({}, {}).", x, y);
+            return true;
+        } else {
+            println!("Invalid coordinates: ({}, {})", x, y);
+        }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
+    }
+    false
 }
 
 use rand::Rng;
 
-pub fn harvest(game_state: &mut MainGameState, x: u32, y: u32) {
-    if let Some(plot) = game_state.plots.get_mut(&(0, 0)) {
+pub fn harvest(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32) -> bool {
+    harvest_plant(game_state, plot, x, y, false)
+}
+
+/// Harvests the fruiting plant at (`x`, `y`), keeping `SEED_SHARE` of the
+/// yield back as a new seed lot. The seeds are self-pollinated offspring of
+/// the plant, mutated according to its `genetic_stability`.
+pub fn collect_seeds(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32) -> bool {
+    harvest_plant(game_state, plot, x, y, true)
+}
+
+fn harvest_plant(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32, collect_seeds: bool) -> bool {
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
         if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
             if let Some(plant) = &tile.plant {
                 if plant.life_cycle_stage == plant::LifeCycleStage::Fruiting {
-                    let yield_amount = rand::thread_rng().gen_range(plant.genetics.yield_range.0..=plant.genetics.yield_range.1);
-                    println!("Harvested {} of {} from ({}, {})", yield_amount, plant.species, x, y);
+                    let full_yield = game_state.rng.gen_range(plant.genetics.yield_range.0..=plant.genetics.yield_range.1);
+                    // Phosphorus shortage means fewer fruit; well-pollinated flowers set more
+                    let yield_amount = (full_yield as f32
+                        * (1.0 - plant.deficiency.phosphorus * PHOSPHORUS_YIELD_PENALTY)
+                        * (1.0 + plant.pollination * POLLINATION_YIELD_BONUS))
+                        .round() as u32;
+                    let mut produce = yield_amount;
+                    if collect_seeds && yield_amount == 0 {
+                        println!("The {} at ({}, {}) set no seed.", plant.species, x, y);
+                    } else if collect_seeds {
+                        let seeds = ((yield_amount as f32 * SEED_SHARE).round() as u32).max(1);
+                        produce = yield_amount - seeds;
+                        let id = game_state.next_id;
+                        game_state.next_id += 1;
+                        let lot = SeedLot {
+                            id,
+                            species: plant.species.clone(),
+                            mother: plant.genome.clone(),
+                            father: plant.genome.clone(),
+                            stability: plant.genetics.genetic_stability,
+                            quantity: seeds,
+                            collected_at: game_state.tick_counter,
+                            parents: vec![plant.id],
+                            generation: plant.generation + 1,
+                        };
+                        game_state.pedigree.insert(id, Ancestor::of_seed_lot(&lot, plant.genetics.clone()));
+                        game_state.seed_lots.insert(id, lot);
+                        println!("Collected {} {} seeds from ({}, {}) as seed lot {}", seeds, plant.species, x, y, id);
+                    }
+                    println!("Harvested {} of {} from ({}, {})", produce, plant.species, x, y);
                     let entry = game_state.inventory.entry(plant.species.clone()).or_insert(0);
-                    *entry += yield_amount;
+                    *entry += produce;
                     tile.plant = None;
+                    return true;
                 } else {
                     println!("The plant at ({}, {}) is not ready to be harvested.", x, y);
                 }
@@ -64,29 +366,82 @@ pub fn harvest(game_state: &mut MainGameState, x: u32, y: u32) {
         } else {
             println!("Invalid coordinates: ({}, {})", x, y);
         }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
     }
+    false
 }
 
 use crate::economy;
 
+use crate::beneficials::{POLLINATION_RATE, POLLINATION_YIELD_BONUS};
+use crate::disease::{Contamination, Disease, DiseaseType, CONTAMINATION_INFECTION_CHANCE};
+use crate::events::GameEvent;
+use crate::garden::Tile;
+use crate::breeding::{find_hybrid, hybrid_name, Hybrid, HYBRID_RARITY, POLLINATION_RANGE, SEEDS_PER_CROSS};
+use crate::catalogue;
+use crate::genome::Genome;
+use crate::lineage::Ancestor;
+use crate::orders::{Order, OrderStatus, OrderUpdate, Side, Trigger};
 use crate::pests::{Pest, PestType};
+use crate::seeds::{self, SeedLot, SEED_SHARE};
+use crate::treatment::{self, Treatment, MAX_RESISTANCE, RESISTANCE_BUILDUP, TREATMENT_CLEAR_LEVEL};
 use rand::seq::SliceRandom;
 
-pub fn process_pests(state: &mut MainGameState) {
-    let mut rng = rand::thread_rng();
+pub fn process_pests(state: &mut MainGameState) -> Vec<PestSighting> {
+    // Beneficial insects gather where there are flowers and prey; bees pollinate as they go
+    for plot in state.plots.values_mut() {
+        for tile in plot.grid.tiles.iter_mut().flatten() {
+            let flowering = tile
+                .plant
+                .as_ref()
+                .is_some_and(|plant| plant.life_cycle_stage == plant::LifeCycleStage::Mature);
+            tile.beneficials.settle(flowering, tile.pest.as_ref());
+            if let Some(plant) = tile.plant.as_mut().filter(|_| flowering) {
+                plant.pollination = (plant.pollination + tile.beneficials.bees * POLLINATION_RATE).min(1.0);
+            }
+        }
+    }
+
+    let rng = &mut state.rng;
+    let weather = state.current_weather;
     let mut pest_updates = Vec::new();
     let mut new_pests = Vec::new();
 
-    for plot in state.plots.values() {
+    let mut sightings = Vec::new();
+
+    for (plot_key, plot) in state.plots.iter() {
         for y in 0..plot.grid.tiles.len() {
             for x in 0..plot.grid.tiles[y].len() {
-                if let Some(pest) = &plot.grid.tiles[y][x].pest {
+                let tile = &plot.grid.tiles[y][x];
+                let Some(plant) = &tile.plant else {
+                    // Pests starve without a host plant
+                    if tile.pest.is_some() {
+                        pest_updates.push((*plot_key, x, y, None));
+                    }
+                    continue;
+                };
+                // Resistant plants shrug off pests; potassium-starved plants succumb more easily
+                let vulnerability = (1.0 - plant.genetics.pest_resistance)
+                    * (1.0 + plant.deficiency.potassium * POTASSIUM_PEST_PENALTY);
+
+                if let Some(pest) = &tile.pest {
+                    let profile = pest.pest_type.profile();
+                    let activity = pest.pest_type.activity(weather, &tile.soil);
                     let mut updated_pest = pest.clone();
-                    updated_pest.infestation_level += 0.05;
-                    pest_updates.push((x, y, updated_pest.clone()));
+                    updated_pest.infestation_level = (updated_pest.infestation_level + profile.growth_rate * activity
+                        - tile.beneficials.predation(&pest.pest_type))
+                    .min(1.0);
+                    if updated_pest.infestation_level <= 0.0 {
+                        println!("Predators have wiped out the {:?} at ({}, {})", pest.pest_type, x, y);
+                        pest_updates.push((*plot_key, x, y, None));
+                        continue;
+                    }
+                    pest_updates.push((*plot_key, x, y, Some(updated_pest.clone())));
 
                     // Pest spreading
-                    if rng.gen_bool(0.2) { // 20% chance to spread
+                    let spread_chance = profile.spread_chance * (0.5 + pest.infestation_level) * activity;
+                    if rng.gen_bool(spread_chance.clamp(0.0, 1.0) as f64) {
                         let mut neighbors = Vec::new();
                         if x > 0 { neighbors.push((x - 1, y)); }
                         let (grid_width, grid_height) = (plot.grid.tiles[y].len(), plot.grid.tiles.len());
@@ -94,115 +449,523 @@ pub fn process_pests(state: &mut MainGameState) {
                         if y > 0 { neighbors.push((x, y - 1)); }
                         if y < grid_height - 1 { neighbors.push((x, y + 1)); }
 
-                        if let Some(&(nx, ny)) = neighbors.choose(&mut rng) {
-                            if plot.grid.tiles[ny][nx].plant.is_some() && plot.grid.tiles[ny][nx].pest.is_none() {
-                                new_pests.push((nx, ny, pest.clone()));
+                        if let Some(&(nx, ny)) = neighbors.choose(rng) {
+                            let target = &plot.grid.tiles[ny][nx];
+                            let resisted = target
+                                .plant
+                                .as_ref()
+                                .is_some_and(|plant| rng.gen_bool(plant.genetics.pest_resistance.clamp(0.0, 1.0) as f64));
+                            if target.plant.is_some() && target.pest.is_none() && !resisted {
+                                new_pests.push((*plot_key, nx, ny, Pest {
+                                    pest_type: pest.pest_type.clone(),
+                                    infestation_level: 0.1,
+                                }));
+                                sightings.push(PestSighting {
+                                    plot: *plot_key,
+                                    x: nx,
+                                    y: ny,
+                                    pest_type: pest.pest_type.clone(),
+                                    spread: true,
+                                });
                             }
                         }
                     }
-                } else if plot.grid.tiles[y][x].plant.is_some() {
-                    if rng.gen_bool(0.1) { // 10% chance of pest appearing
-                        let pest_type = match rng.gen_range(0..3) {
-                            0 => PestType::Aphids,
-                            1 => PestType::SpiderMites,
-                            _ => PestType::Whiteflies,
-                        };
-                        new_pests.push((x, y, Pest {
-                            pest_type: pest_type.clone(),
-                            infestation_level: 0.1,
-                        }));
-                        println!("A pest has appeared: {:?} at ({}, {})", pest_type, x, y);
+                } else {
+                    for pest_type in PestType::ALL {
+                        let chance = pest_type.spawn_chance(weather, &tile.soil) * vulnerability;
+                        if rng.gen_bool(chance.clamp(0.0, 1.0) as f64) {
+                            new_pests.push((*plot_key, x, y, Pest {
+                                pest_type: pest_type.clone(),
+                                infestation_level: 0.1,
+                            }));
+                            sightings.push(PestSighting {
+                                plot: *plot_key,
+                                x,
+                                y,
+                                pest_type: pest_type.clone(),
+                                spread: false,
+                            });
+                            println!("A pest has appeared: {:?} at ({}, {})", pest_type, x, y);
+                            break;
+                        }
                     }
                 }
             }
         }
     }
 
-    for plot in state.plots.values_mut() {
-        for (x, y, pest) in &pest_updates {
-            if let Some(plant) = &mut plot.grid.tiles[*y][*x].plant {
-                plant.health -= pest.infestation_level * 0.1;
-                println!("Pest at ({}, {}) is damaging the plant. Plant health: {}", x, y, plant.health);
+    for (plot_key, x, y, pest) in pest_updates {
+        if let Some(plot) = state.plots.get_mut(&plot_key) {
+            let tile = &mut plot.grid.tiles[y][x];
+            if let (Some(plant), Some(pest)) = (&mut tile.plant, &pest) {
+                let profile = pest.pest_type.profile();
+                let vulnerability = (1.0 - plant.genetics.pest_resistance)
+                    * (1.0 + plant.deficiency.potassium * POTASSIUM_PEST_PENALTY);
+                let damage = pest.infestation_level
+                    * profile.health_damage
+                    * pest.pest_type.activity(weather, &tile.soil)
+                    * vulnerability;
+                plant.health = (plant.health - damage).max(0.0);
+                println!("{:?} at ({}, {}) are damaging the plant. Plant health: {}", pest.pest_type, x, y, plant.health);
             }
-            plot.grid.tiles[*y][*x].pest = Some(pest.clone());
+            tile.pest = pest;
         }
-        for (x, y, pest) in &new_pests {
-            plot.grid.tiles[*y][*x].pest = Some(pest.clone());
+    }
+    for (plot_key, x, y, pest) in new_pests {
+        if let Some(plot) = state.plots.get_mut(&plot_key) {
+            plot.grid.tiles[y][x].pest = Some(pest);
             println!("Pest has spread to ({}, {})", x, y);
         }
     }
+
+    sightings
 }
 
-pub fn process_weather(state: &mut MainGameState) {
-    let mut rng = rand::thread_rng();
-    let next_weather = [
+/// The in-bounds tiles among the eight surrounding (`x`, `y`).
+fn surrounding_tiles(x: usize, y: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+    let mut tiles = Vec::new();
+    for ny in y.saturating_sub(1)..(y + 2).min(height) {
+        for nx in x.saturating_sub(1)..(x + 2).min(width) {
+            if (nx, ny) != (x, y) {
+                tiles.push((nx, ny));
+            }
+        }
+    }
+    tiles
+}
+
+/// Incubates, worsens and spreads diseases, infects new plants, and keeps
+/// soil-borne diseases lingering in the soil. Returns the diseases whose
+/// symptoms showed this tick.
+pub fn process_diseases(state: &mut MainGameState) -> Vec<DiseaseSighting> {
+    let rng = &mut state.rng;
+    let weather = state.current_weather;
+    let mut infections = Vec::new();
+    let mut sightings = Vec::new();
+
+    for (plot_key, plot) in state.plots.iter_mut() {
+        let (width, height) = (plot.width() as usize, plot.height() as usize);
+        let living: Vec<Vec<bool>> = plot
+            .grid
+            .tiles
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| {
+                        tile.plant
+                            .as_ref()
+                            .is_some_and(|plant| plant.life_cycle_stage != plant::LifeCycleStage::Dead)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for y in 0..height {
+            for x in 0..width {
+                let neighbours: Vec<(usize, usize)> = surrounding_tiles(x, y, width, height)
+                    .into_iter()
+                    .filter(|&(nx, ny)| living[ny][nx])
+                    .collect();
+                let crowding = neighbours.len() as f32 / 8.0;
+
+                let Tile { soil, plant, disease, .. } = &mut plot.grid.tiles[y][x];
+                if let Some(contamination) = &mut soil.contamination {
+                    contamination.ticks_remaining = contamination.ticks_remaining.saturating_sub(1);
+                    if contamination.ticks_remaining == 0 {
+                        soil.contamination = None;
+                    }
+                }
+                let Some(plant) = plant else {
+                    *disease = None;
+                    continue;
+                };
+                let resistance = plant.genetics.disease_resistance.clamp(0.0, 1.0);
+
+                match disease {
+                    Some(disease) => {
+                        let profile = disease.disease_type.profile();
+                        // Symptomatic plants shed soil-borne spores into the tile
+                        if profile.contamination_ticks > 0 && disease.is_visible() {
+                            soil.contamination = Some(Contamination {
+                                disease_type: disease.disease_type,
+                                ticks_remaining: profile.contamination_ticks,
+                            });
+                        }
+                        if plant.life_cycle_stage == plant::LifeCycleStage::Dead {
+                            continue;
+                        }
+
+                        if disease.incubation > 0 {
+                            disease.incubation -= 1;
+                            if disease.is_visible() {
+                                println!(
+                                    "{:?} spotted on the {} at ({}, {}): {}",
+                                    disease.disease_type,
+                                    plant.species,
+                                    x,
+                                    y,
+                                    disease.disease_type.symptoms()
+                                );
+                                sightings.push(DiseaseSighting {
+                                    plot: *plot_key,
+                                    x,
+                                    y,
+                                    species: plant.species.clone(),
+                                    disease_type: disease.disease_type,
+                                });
+                            }
+                        } else {
+                            disease.severity = (disease.severity + profile.progression).min(1.0);
+                            let damage = disease.severity * profile.health_damage * (1.0 - resistance);
+                            plant.health = (plant.health - damage).max(0.0);
+                        }
+
+                        // Infected plants pass the disease on before anyone can see it
+                        let spread_chance = profile.spread_chance * (0.5 + disease.severity);
+                        if rng.gen_bool(spread_chance.clamp(0.0, 1.0) as f64) {
+                            if let Some(&(nx, ny)) = neighbours.choose(rng) {
+                                infections.push((*plot_key, nx, ny, disease.disease_type));
+                            }
+                        }
+                    }
+                    None if plant.life_cycle_stage != plant::LifeCycleStage::Dead => {
+                        for disease_type in DiseaseType::ALL {
+                            let mut chance = disease_type.infection_chance(weather, soil, crowding);
+                            if soil
+                                .contamination
+                                .as_ref()
+                                .is_some_and(|contamination| contamination.disease_type == disease_type)
+                            {
+                                chance += CONTAMINATION_INFECTION_CHANCE;
+                            }
+                            if rng.gen_bool((chance * (1.0 - resistance)).clamp(0.0, 1.0) as f64) {
+                                *disease = Some(Disease::new(disease_type));
+                                break;
+                            }
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    for (plot_key, x, y, disease_type) in infections {
+        if let Some(plot) = state.plots.get_mut(&plot_key) {
+            let tile = &mut plot.grid.tiles[y][x];
+            if let Some(plant) = &tile.plant {
+                let resistance = plant.genetics.disease_resistance.clamp(0.0, 1.0);
+                if tile.disease.is_none() && !state.rng.gen_bool(resistance as f64) {
+                    tile.disease = Some(Disease::new(disease_type));
+                }
+            }
+        }
+    }
+
+    sightings
+}
+
+/// Weed pressure gained per tick on bare, moist, fertile soil, before crowding slows it.
+pub const WEED_GROWTH: f32 = 0.08;
+/// Chance per tick that weeds germinate on a weed-free tile.
+pub const WEED_GERMINATION_CHANCE: f64 = 0.03;
+/// Chance per tick that a heavily weeded tile seeds each weed-free neighbour.
+pub const WEED_SPREAD_CHANCE: f64 = 0.1;
+/// Weed pressure above which a tile starts seeding its neighbours.
+pub const WEED_SPREAD_THRESHOLD: f32 = 0.5;
+/// Moisture and each nutrient drawn per tick by a fully weeded tile.
+pub const WEED_RESOURCE_USE: f32 = 0.02;
+/// Share of weed growth and germination that mulch still lets through.
+pub const MULCH_SUPPRESSION: f32 = 0.1;
+
+pub fn process_weeds(state: &mut MainGameState) {
+    let rng = &mut state.rng;
+    let mut spreads = Vec::new();
+
+    for (plot_key, plot) in state.plots.iter_mut() {
+        let (grid_width, grid_height) = (plot.width() as usize, plot.height() as usize);
+        for y in 0..grid_height {
+            for x in 0..grid_width {
+                let tile = &mut plot.grid.tiles[y][x];
+                let soil = &mut tile.soil;
+                let nutrients = &soil.soil_nutrients;
+                let fertility = (nutrients.nitrogen + nutrients.phosphorus + nutrients.potassium) / 3.0;
+                // Weeds thrive on bare, wet, fertile ground; plants shade them and mulch smothers them
+                let mut vigour = (0.5 + soil.soil_moisture) * (0.5 + fertility);
+                if tile.plant.is_some() {
+                    vigour *= 0.5;
+                }
+                if soil.mulch > 0 {
+                    vigour *= MULCH_SUPPRESSION;
+                    soil.mulch -= 1;
+                }
+
+                if soil.weeds > 0.0 {
+                    soil.weeds += WEED_GROWTH * vigour * (1.0 - soil.weeds);
+                } else if rng.gen_bool((WEED_GERMINATION_CHANCE * vigour as f64).min(1.0)) {
+                    soil.weeds = 0.05;
+                }
+                soil.weeds = soil.weeds.clamp(0.0, 1.0);
+
+                // Weeds compete with plants for water and nutrients
+                let used = soil.weeds * WEED_RESOURCE_USE;
+                soil.soil_moisture = (soil.soil_moisture - used).max(0.0);
+                soil.soil_nutrients.nitrogen = (soil.soil_nutrients.nitrogen - used).max(0.0);
+                soil.soil_nutrients.phosphorus = (soil.soil_nutrients.phosphorus - used).max(0.0);
+                soil.soil_nutrients.potassium = (soil.soil_nutrients.potassium - used).max(0.0);
+
+                if soil.weeds > WEED_SPREAD_THRESHOLD {
+                    let mut neighbors = Vec::new();
+                    if x > 0 { neighbors.push((x - 1, y)); }
+                    if x < grid_width - 1 { neighbors.push((x + 1, y)); }
+                    if y > 0 { neighbors.push((x, y - 1)); }
+                    if y < grid_height - 1 { neighbors.push((x, y + 1)); }
+                    for (nx, ny) in neighbors {
+                        if rng.gen_bool(WEED_SPREAD_CHANCE) {
+                            spreads.push((*plot_key, nx, ny));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for (plot_key, x, y) in spreads {
+        if let Some(plot) = state.plots.get_mut(&plot_key) {
+            let soil = &mut plot.grid.tiles[y][x].soil;
+            if soil.weeds == 0.0 && soil.mulch == 0 {
+                soil.weeds = 0.05;
+            }
+        }
+    }
+}
+
+fn roll_weather(rng: &mut impl Rng) -> Weather {
+    *[
         Weather::Sunny,
         Weather::Cloudy,
         Weather::Rainy,
         Weather::Heatwave,
     ]
-    .choose(&mut rng)
-    .unwrap();
-    state.current_weather = *next_weather;
+    .choose(rng)
+    .unwrap()
+}
+
+pub fn process_weather(state: &mut MainGameState) {
+    state.current_weather = roll_weather(&mut state.rng);
     println!("Weather updated to: {:?}", state.current_weather);
 }
 
-pub fn process_plants(state: &mut MainGameState) {
-    for plot in state.plots.values_mut() {
-        for row in plot.grid.tiles.iter_mut() {
-            for tile in row.iter_mut() {
+/// Health regained each tick by a plant in ideal conditions.
+pub const HEALTH_RECOVERY: f32 = 0.05;
+/// Health lost each tick while soil moisture is outside the plant's ideal range.
+pub const MOISTURE_STRESS: f32 = 0.02;
+/// Health lost each tick during a heatwave.
+pub const HEAT_STRESS: f32 = 0.02;
+/// Growth lost by a plant with no nitrogen available at all.
+pub const NITROGEN_GROWTH_PENALTY: f32 = 0.6;
+/// Health lost per tick by a plant at the worst level of any deficiency.
+pub const DEFICIENCY_HEALTH_DAMAGE: f32 = 0.03;
+/// Yield lost by a plant with no phosphorus available at harvest.
+pub const PHOSPHORUS_YIELD_PENALTY: f32 = 0.6;
+/// Extra pest pressure on a plant with no potassium available, as a multiplier on top of 1.0.
+pub const POTASSIUM_PEST_PENALTY: f32 = 1.0;
+/// The least growth a plant manages in deep shade.
+pub const MIN_LIGHT_GROWTH: f32 = 0.1;
+/// Growth lost by a plant on a fully weeded tile.
+pub const WEED_COMPETITION: f32 = 0.3;
+/// Growth lost at full treatment residue.
+pub const RESIDUE_GROWTH_PENALTY: f32 = 0.5;
+/// How many ages past `wither_time` a withered plant lasts before it rots into the soil.
+pub const DECAY_AGE: u32 = 3;
+/// Nutrients returned to the soil when a withered plant decays.
+pub const DECAY_NUTRIENT_RETURN: f32 = 0.1;
+
+pub fn process_plants(state: &mut MainGameState) -> Vec<StageChange> {
+    let mut changes = Vec::new();
+    let season = Season::from_tick(state.tick_counter);
+
+    for (plot_key, plot) in state.plots.iter_mut() {
+        let light_levels = light::light_levels(plot, state.current_weather, season);
+        for (y, row) in plot.grid.tiles.iter_mut().enumerate() {
+            for (x, tile) in row.iter_mut().enumerate() {
                 if let Some(plant) = &mut tile.plant {
+                    if plant.life_cycle_stage == plant::LifeCycleStage::Dead {
+                        continue;
+                    }
+                    let previous_stage = plant.life_cycle_stage.clone();
                     let mut growth_rate = 1.0;
+                    let mut ideal_conditions =
+                        tile.pest.is_none() && !tile.disease.as_ref().is_some_and(Disease::is_visible);
 
                     if state.current_weather == Weather::Heatwave {
                         growth_rate *= 0.5; // 50% growth reduction during heatwave
+                        plant.health -= HEAT_STRESS;
+                        ideal_conditions = false;
                     }
 
                     // Check moisture levels
                     let (min_moisture, max_moisture) = plant.genetics.ideal_moisture_range;
                     if tile.soil.soil_moisture < min_moisture || tile.soil.soil_moisture > max_moisture {
                         growth_rate *= 0.8; // 20% growth reduction if outside ideal moisture
+                        plant.health -= MOISTURE_STRESS;
+                        ideal_conditions = false;
                     }
 
-                    plant.growth_progress += growth_rate;
+                    // A plant starved by the wrong pH grows at the pace of its scarcest nutrient
+                    let availability = plant.genetics.nutrient_availability(tile.soil.soil_ph);
+                    let uptake = availability.0.min(availability.1).min(availability.2);
+                    if uptake < 1.0 {
+                        growth_rate *= uptake;
+                        ideal_conditions = false;
+                    }
+
+                    // Nitrogen shortage stunts growth; any shortage wears the plant down
+                    plant.deficiency = plant::Deficiency::assess(&tile.soil.soil_nutrients, availability);
+                    if plant.deficiency.worst() > 0.0 {
+                        growth_rate *= 1.0 - plant.deficiency.nitrogen * NITROGEN_GROWTH_PENALTY;
+                        plant.health -= plant.deficiency.worst() * DEFICIENCY_HEALTH_DAMAGE;
+                        ideal_conditions = false;
+                    }
 
-                    if plant.growth_progress >= 1.0 {
-                        plant.age += 1;
-                        plant.growth_progress -= 1.0;
+                    // Too little sun, from dull weather, short days or taller neighbours, slows growth
+                    let light = light_levels[y][x];
+                    if light < plant.genetics.light_req {
+                        growth_rate *= (light / plant.genetics.light_req).max(MIN_LIGHT_GROWTH);
                     }
 
-                    if plant.age >= plant.wither_time {
-                        plant.life_cycle_stage = plant::LifeCycleStage::Withering;
-                    } else if plant.age >= plant.maturity_age {
-                        plant.life_cycle_stage = plant::LifeCycleStage::Fruiting;
-                    } else if plant.age >= plant.maturity_age / 2 {
-                        plant.life_cycle_stage = plant::LifeCycleStage::Growing;
-                    } else if plant.age > 0 {
-                        plant.life_cycle_stage = plant::LifeCycleStage::Sprout;
+                    // Sap-sucking pests stunt growth
+                    if let Some(pest) = &tile.pest {
+                        growth_rate *= 1.0 - pest.infestation_level * pest.pest_type.profile().growth_damage;
+                    }
+
+                    // Chemical residue in the soil holds roots back
+                    growth_rate *= 1.0 - tile.soil.residue * RESIDUE_GROWTH_PENALTY;
+
+                    // Weeds crowd the plant out
+                    growth_rate *= 1.0 - tile.soil.weeds * WEED_COMPETITION;
+
+                    // Roots drown in waterlogged, poorly aerated soil
+                    if tile.soil.soil_moisture > FIELD_CAPACITY {
+                        growth_rate *= tile.soil.soil_type.properties().waterlogged_growth_factor();
+                    }
+
+                    if ideal_conditions {
+                        plant.health += HEALTH_RECOVERY;
+                    }
+                    plant.health = plant.health.clamp(0.0, 1.0);
+
+                    if plant.health <= 0.0 {
+                        plant.life_cycle_stage = plant::LifeCycleStage::Dead;
+                        println!("The {} at ({}, {}) has died.", plant.species, x, y);
+                    } else {
+                        plant.growth_progress += growth_rate;
+
+                        if plant.growth_progress >= 1.0 {
+                            plant.age += 1;
+                            plant.growth_progress -= 1.0;
+                        }
+
+                        plant.life_cycle_stage = plant.stage_for_age();
+                    }
+
+                    if plant.life_cycle_stage != previous_stage {
+                        changes.push(StageChange {
+                            plot: *plot_key,
+                            x,
+                            y,
+                            species: plant.species.clone(),
+                            stage: plant.life_cycle_stage.clone(),
+                        });
+                    }
+
+                    if plant.life_cycle_stage == plant::LifeCycleStage::Withering
+                        && plant.age >= plant.wither_time + DECAY_AGE
+                    {
+                        println!("The withered {} at ({}, {}) has decayed into the soil.", plant.species, x, y);
+                        let nutrients = &mut tile.soil.soil_nutrients;
+                        nutrients.nitrogen = (nutrients.nitrogen + DECAY_NUTRIENT_RETURN).min(1.0);
+                        nutrients.phosphorus = (nutrients.phosphorus + DECAY_NUTRIENT_RETURN).min(1.0);
+                        nutrients.potassium = (nutrients.potassium + DECAY_NUTRIENT_RETURN).min(1.0);
+                        tile.plant = None;
+                        tile.pest = None;
+                        tile.disease = None;
                     }
                 }
             }
         }
     }
+
+    changes
 }
 
+/// Nutrients washed out of loam by a tick of rain; sand loses more, clay less.
+pub const RAIN_LEACHING: f32 = 0.02;
+/// Ticks an amendment takes to be worked into the soil.
+pub const AMENDMENT_TICKS: u32 = 5;
+/// What one soil amendment costs per tile.
+pub const AMENDMENT_COST: Money = Money::from_cents(100);
+/// How far one application of lime raises, or of sulfur lowers, soil pH.
+pub const PH_AMENDMENT_SHIFT: f32 = 0.5;
+/// The most soil pH moves in one tick while lime or sulfur works in.
+pub const PH_SHIFT_PER_TICK: f32 = 0.1;
+pub const MIN_SOIL_PH: f32 = 4.0;
+pub const MAX_SOIL_PH: f32 = 9.0;
+
+/// Treatment residue broken down per tick.
+pub const RESIDUE_DECAY: f32 = 0.01;
+
 pub fn process_environment(state: &mut MainGameState) {
     for plot in state.plots.values_mut() {
         for row in plot.grid.tiles.iter_mut() {
             for tile in row.iter_mut() {
+                let soil = tile.soil.soil_type.properties();
                 match state.current_weather {
-                    Weather::Sunny => tile.soil.soil_moisture -= 0.05,
-                    Weather::Rainy => tile.soil.soil_moisture += 0.2,
-                    Weather::Heatwave => tile.soil.soil_moisture -= 0.1,
+                    Weather::Sunny => tile.soil.soil_moisture -= 0.05 * soil.evaporation_factor(),
+                    Weather::Rainy => {
+                        tile.soil.soil_moisture += 0.2 * soil.absorption_factor();
+                        let leached = RAIN_LEACHING * 2.0 * (1.0 - soil.nutrient_retention);
+                        tile.soil.soil_nutrients.nitrogen -= leached;
+                        tile.soil.soil_nutrients.phosphorus -= leached;
+                        tile.soil.soil_nutrients.potassium -= leached;
+                    }
+                    Weather::Heatwave => tile.soil.soil_moisture -= 0.1 * soil.evaporation_factor(),
                     _ => {}
                 }
 
+                // Water above field capacity drains away at the soil's drainage rate
+                if tile.soil.soil_moisture > FIELD_CAPACITY {
+                    tile.soil.soil_moisture -= (tile.soil.soil_moisture - FIELD_CAPACITY) * soil.drainage;
+                }
+
+                // Treatment residue breaks down
+                tile.soil.residue = (tile.soil.residue - RESIDUE_DECAY).max(0.0);
+
+                // Lime and sulfur work in gradually
+                if tile.soil.pending_ph_shift != 0.0 {
+                    let step = tile.soil.pending_ph_shift.clamp(-PH_SHIFT_PER_TICK, PH_SHIFT_PER_TICK);
+                    tile.soil.soil_ph = (tile.soil.soil_ph + step).clamp(MIN_SOIL_PH, MAX_SOIL_PH);
+                    tile.soil.pending_ph_shift -= step;
+                    if tile.soil.pending_ph_shift.abs() < 1e-4 {
+                        tile.soil.pending_ph_shift = 0.0;
+                    }
+                }
+
+                if let Some(amendment) = &mut tile.soil.amendment {
+                    amendment.ticks_remaining = amendment.ticks_remaining.saturating_sub(1);
+                    if amendment.ticks_remaining == 0 {
+                        tile.soil.soil_type = amendment.kind.apply_to(tile.soil.soil_type);
+                        tile.soil.amendment = None;
+                    }
+                }
+
                 if let Some(plant) = &mut tile.plant {
-                    // Consume water and nutrients
+                    // Consume water and nutrients; soil pH limits how much of each nutrient the roots can take up
+                    let (n, p, k) = plant.genetics.nutrient_availability(tile.soil.soil_ph);
                     tile.soil.soil_moisture -= 0.01; // Constant water consumption for now
-                    tile.soil.soil_nutrients.nitrogen -= plant.genetics.nutrient_consumption.0;
-                    tile.soil.soil_nutrients.phosphorus -= plant.genetics.nutrient_consumption.1;
-                    tile.soil.soil_nutrients.potassium -= plant.genetics.nutrient_consumption.2;
+                    tile.soil.soil_nutrients.nitrogen -= plant.genetics.nutrient_consumption.0 * n;
+                    tile.soil.soil_nutrients.phosphorus -= plant.genetics.nutrient_consumption.1 * p;
+                    tile.soil.soil_nutrients.potassium -= plant.genetics.nutrient_consumption.2 * k;
                 }
 
                 // Clamp soil values
@@ -215,7 +978,46 @@ pub fn process_environment(state: &mut MainGameState) {
     }
 }
 
-pub fn run_game_tick(state: &mut MainGameState, weather: Option<Weather>) {
+/// A plant that moved to a new life cycle stage during a tick.
+pub struct StageChange {
+    pub plot: (i32, i32),
+    pub x: usize,
+    pub y: usize,
+    pub species: String,
+    pub stage: plant::LifeCycleStage,
+}
+
+/// A pest that appeared on, or spread to, a tile during a tick.
+pub struct PestSighting {
+    pub plot: (i32, i32),
+    pub x: usize,
+    pub y: usize,
+    pub pest_type: PestType,
+    pub spread: bool,
+}
+
+/// A disease whose symptoms first showed on a tile during a tick.
+pub struct DiseaseSighting {
+    pub plot: (i32, i32),
+    pub x: usize,
+    pub y: usize,
+    pub species: String,
+    pub disease_type: DiseaseType,
+}
+
+/// What happened during a single call to `run_game_tick`.
+pub struct TickSummary {
+    pub tick: u64,
+    pub weather: Weather,
+    pub stage_changes: Vec<StageChange>,
+    pub pest_sightings: Vec<PestSighting>,
+    pub disease_sightings: Vec<DiseaseSighting>,
+    pub order_updates: Vec<OrderUpdate>,
+    /// Land tax charged this tick, if it was a tax tick.
+    pub land_tax: Money,
+}
+
+pub fn run_game_tick(state: &mut MainGameState, weather: Option<Weather>) -> TickSummary {
     state.tick_counter += 1;
 
     if let Some(weather) = weather {
@@ -224,41 +1026,748 @@ pub fn run_game_tick(state: &mut MainGameState, weather: Option<Weather>) {
         process_weather(state);
     }
     process_environment(state);
-    process_plants(state);
-    process_pests(state);
+    let stage_changes = process_plants(state);
+    process_weeds(state);
+    let pest_sightings = process_pests(state);
+    treatment::relax_resistance(&mut state.treatment_resistance);
+    let disease_sightings = process_diseases(state);
+    for sighting in &disease_sightings {
+        if sighting.disease_type == DiseaseType::Blight {
+            state.events.push(GameEvent::BlightSpotted);
+        }
+    }
+
+    economy::update_market_prices(&mut state.market, &mut state.rng);
+    let order_updates = process_orders(state);
+    let land_tax = charge_land_tax(state);
+
+    TickSummary {
+        tick: state.tick_counter,
+        weather: state.current_weather,
+        stage_changes,
+        pest_sightings,
+        disease_sightings,
+        order_updates,
+        land_tax,
+    }
+}
+
+/// Every `tax_period` ticks, charges the land tax on every tile owned.
+/// The tax is taken even if it leaves the wallet overdrawn.
+pub fn charge_land_tax(state: &mut MainGameState) -> Money {
+    let period = state.fees.tax_period;
+    if period == 0 || !state.tick_counter.is_multiple_of(period) {
+        return Money::ZERO;
+    }
+    // More land than a u32 can count is taxed as if it were u32::MAX tiles
+    let tiles = state
+        .plots
+        .values()
+        .try_fold(0u32, |tiles, plot| plot.width().checked_mul(plot.height())?.checked_add(tiles))
+        .unwrap_or(u32::MAX);
+    let tax = state.fees.land_tax_per_tile.times(tiles);
+    if tax != Money::ZERO {
+        state.ledger.pay(&mut state.wallet, state.tick_counter, TransactionKind::Tax, "Land tax", tiles, tax);
+    }
+    tax
+}
+
+/// Sells `quantity` of the produce `item`, paying the transaction fee and
+/// recording the sale. Returns the proceeds after the fee.
+pub fn sell(game_state: &mut MainGameState, item: &str, quantity: u32) -> Result<Money, String> {
+    let gross = economy::sell_item(&mut game_state.inventory, &mut game_state.market, item, quantity)?;
+    let fee = gross.scale(game_state.fees.transaction_rate);
+    game_state.ledger.record(
+        &mut game_state.wallet,
+        game_state.tick_counter,
+        TransactionKind::Sale,
+        item,
+        quantity,
+        gross,
+        fee,
+        gross - fee,
+    );
+    Ok(gross - fee)
+}
+
+/// Buys `quantity` of `item` if the wallet covers it and the transaction
+/// fee, recording the purchase. Returns what it cost, fee included.
+pub fn buy(game_state: &mut MainGameState, item: &str, quantity: u32) -> Result<Money, String> {
+    let gross = economy::quote_purchase(&game_state.market, item, quantity)?;
+    let fee = gross.scale(game_state.fees.transaction_rate);
+    if game_state.wallet < gross + fee {
+        return Err(format!("Not enough cash: that costs ${} with fees.", gross + fee));
+    }
+    economy::buy_item(
+        &mut game_state.inventory,
+        &mut game_state.seed_inventory,
+        &mut game_state.market,
+        item,
+        quantity,
+    )?;
+    game_state.ledger.record(
+        &mut game_state.wallet,
+        game_state.tick_counter,
+        TransactionKind::Purchase,
+        item,
+        quantity,
+        gross,
+        fee,
+        -(gross + fee),
+    );
+    Ok(gross + fee)
+}
+
+/// Places a standing order to trade `quantity` of `item` once its price
+/// meets `trigger`, returning the order's id. An order with `expires` set is
+/// dropped if it has not filled within that many ticks.
+pub fn place_order(
+    game_state: &mut MainGameState,
+    side: Side,
+    item: &str,
+    quantity: u32,
+    trigger: Trigger,
+    expires: Option<u64>,
+) -> Result<u32, String> {
+    let listing = game_state
+        .market
+        .listings
+        .get(item)
+        .ok_or_else(|| format!("'{}' is not traded", item))?;
+    match (side, listing.kind) {
+        (Side::Sell, ItemKind::Produce) | (Side::Buy, ItemKind::Seed | ItemKind::Supply) => {}
+        (Side::Sell, _) => return Err("Only produce can be sold".to_string()),
+        (Side::Buy, _) => return Err("Produce can only be sold, not bought".to_string()),
+    }
+    if quantity == 0 {
+        return Err("An order needs a quantity of at least 1".to_string());
+    }
+    if !(trigger.price().is_finite() && trigger.price() > 0.0) {
+        return Err("An order needs a trigger price above zero".to_string());
+    }
+    let expires_at = match expires {
+        Some(ticks) => Some(
+            game_state
+                .tick_counter
+                .checked_add(ticks)
+                .ok_or("An order cannot expire that far in the future")?,
+        ),
+        None => None,
+    };
+    let id = game_state.next_order_id;
+    game_state.next_order_id += 1;
+    game_state.orders.insert(
+        id,
+        Order {
+            id,
+            side,
+            item: item.to_string(),
+            remaining: quantity,
+            trigger,
+            placed_at: game_state.tick_counter,
+            expires_at,
+        },
+    );
+    Ok(id)
+}
+
+pub fn cancel_order(game_state: &mut MainGameState, id: u32) -> Result<Order, String> {
+    game_state.orders.remove(&id).ok_or_else(|| format!("There is no open order #{}", id))
+}
+
+/// Fills whatever the market now allows of each standing order, oldest
+/// first, and drops the orders that have expired. Sell orders are limited to
+/// the produce held and buy orders to the cash in the wallet.
+pub fn process_orders(state: &mut MainGameState) -> Vec<OrderUpdate> {
+    let mut updates = Vec::new();
+    let ids: Vec<u32> = state.orders.keys().copied().collect();
+    for id in ids {
+        let order = state.orders[&id].clone();
+        let update = |quantity: u32, value: Money, status: OrderStatus| OrderUpdate {
+            order_id: id,
+            side: order.side,
+            item: order.item.clone(),
+            quantity,
+            value,
+            status,
+        };
+        if order.expires_at.is_some_and(|tick| state.tick_counter > tick) {
+            state.orders.remove(&id);
+            updates.push(update(0, Money::ZERO, OrderStatus::Expired));
+            continue;
+        }
+        if !state.market.price(&order.item).is_some_and(|price| order.trigger.is_met(price)) {
+            continue;
+        }
+        // Fills go in the ledger as one trade per order per tick
+        let rate = state.fees.transaction_rate;
+        let (mut filled, mut gross, mut fees) = (0, Money::ZERO, Money::ZERO);
+        while filled < order.remaining {
+            if order.is_limit() && !state.market.price(&order.item).is_some_and(|price| order.trigger.is_met(price)) {
+                break;
+            }
+            let traded = match order.side {
+                Side::Sell => economy::sell_item(&mut state.inventory, &mut state.market, &order.item, 1),
+                Side::Buy => economy::quote_purchase(&state.market, &order.item, 1)
+                    .and_then(|cost| {
+                        let spent = gross + fees;
+                        if state.wallet - spent < cost + cost.scale(rate) {
+                            Err("Not enough cash.")
+                        } else {
+                            Ok(())
+                        }
+                    })
+                    .and_then(|_| {
+                        economy::buy_item(
+                            &mut state.inventory,
+                            &mut state.seed_inventory,
+                            &mut state.market,
+                            &order.item,
+                            1,
+                        )
+                    }),
+            };
+            match traded {
+                Ok(amount) => {
+                    filled += 1;
+                    gross += amount;
+                    fees += amount.scale(rate);
+                }
+                Err(_) => break,
+            }
+        }
+        if filled == 0 {
+            continue;
+        }
+        let (kind, value) = match order.side {
+            Side::Sell => (TransactionKind::Sale, gross - fees),
+            Side::Buy => (TransactionKind::Purchase, -(gross + fees)),
+        };
+        state.ledger.record(&mut state.wallet, state.tick_counter, kind, &order.item, filled, gross, fees, value);
+        let status = if filled == order.remaining {
+            state.orders.remove(&id);
+            OrderStatus::Filled
+        } else {
+            if let Some(open) = state.orders.get_mut(&id) {
+                open.remaining -= filled;
+            }
+            OrderStatus::PartiallyFilled
+        };
+        updates.push(update(filled, value.abs(), status));
+    }
+    updates
+}
+
+/// How many price moves `AdvanceSummary::report` lists.
+pub const REPORTED_PRICE_MOVES: usize = 10;
+
+/// The combined result of running several ticks with `advance`.
+pub struct AdvanceSummary {
+    pub ticks: Vec<TickSummary>,
+    /// Market prices as (item, price before, price after).
+    pub price_moves: Vec<(String, f32, f32)>,
+}
+
+/// Runs `ticks` game ticks, optionally forcing the weather for each of them.
+pub fn advance(state: &mut MainGameState, ticks: u64, weather: Option<Weather>) -> AdvanceSummary {
+    let prices_before: Vec<(String, f32)> = state
+        .market
+        .listings
+        .iter()
+        .map(|(item, listing)| (item.clone(), listing.price))
+        .collect();
+    let ticks = (0..ticks).map(|_| run_game_tick(state, weather)).collect();
+    let price_moves = prices_before
+        .into_iter()
+        .filter_map(|(item, before)| state.market.price(&item).map(|after| (item, before, after)))
+        .collect();
+
+    AdvanceSummary { ticks, price_moves }
+}
+
+impl AdvanceSummary {
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        if let (Some(first), Some(last)) = (self.ticks.first(), self.ticks.last()) {
+            report.push_str(&format!("Advanced {} tick(s): {} -> {}\n", self.ticks.len(), first.tick - 1, last.tick));
+        } else {
+            report.push_str("Advanced 0 ticks.\n");
+        }
+
+        let mut weather_counts: Vec<(Weather, u32)> = Vec::new();
+        for tick in &self.ticks {
+            match weather_counts.iter_mut().find(|(weather, _)| *weather == tick.weather) {
+                Some((_, count)) => *count += 1,
+                None => weather_counts.push((tick.weather, 1)),
+            }
+        }
+        if !weather_counts.is_empty() {
+            let tally: Vec<String> = weather_counts
+                .iter()
+                .map(|(weather, count)| format!("{:?} x{}", weather, count))
+                .collect();
+            report.push_str(&format!("Weather: {}\n", tally.join(", ")));
+        }
+
+        for tick in &self.ticks {
+            for change in &tick.stage_changes {
+                report.push_str(&format!(
+                    "Tick {}: {} at {:?} ({}, {}) is now {:?}\n",
+                    tick.tick, change.species, change.plot, change.x, change.y, change.stage
+                ));
+            }
+            for sighting in &tick.pest_sightings {
+                let verb = if sighting.spread { "spread to" } else { "appeared at" };
+                report.push_str(&format!(
+                    "Tick {}: {:?} {} {:?} ({}, {})\n",
+                    tick.tick, sighting.pest_type, verb, sighting.plot, sighting.x, sighting.y
+                ));
+            }
+            for sighting in &tick.disease_sightings {
+                report.push_str(&format!(
+                    "Tick {}: {:?} spotted on {} at {:?} ({}, {})\n",
+                    tick.tick, sighting.disease_type, sighting.species, sighting.plot, sighting.x, sighting.y
+                ));
+            }
+            for update in &tick.order_updates {
+                report.push_str(&format!("Tick {}: {}\n", tick.tick, update.describe()));
+            }
+            if tick.land_tax != Money::ZERO {
+                report.push_str(&format!("Tick {}: paid ${} land tax\n", tick.tick, tick.land_tax));
+            }
+        }
+
+        // The catalogue makes for hundreds of items, so only the biggest movers are shown
+        let mut moves: Vec<(&String, f32, f32, f32)> = self
+            .price_moves
+            .iter()
+            .map(|(item, before, after)| {
+                let change = if *before > 0.0 { (after - before) / before * 100.0 } else { 0.0 };
+                (item, *before, *after, change)
+            })
+            .collect();
+        moves.sort_by(|a, b| b.3.abs().total_cmp(&a.3.abs()));
+        report.push_str("Biggest price moves:\n");
+        for (item, before, after, change) in moves.into_iter().take(REPORTED_PRICE_MOVES) {
+            report.push_str(&format!("{}\t\t{:.2} -> {:.2} ({:+.1}%)\n", item, before, after, change));
+        }
+        report
+    }
+}
+
+/// What one tile of new land costs.
+pub const LAND_PRICE_PER_TILE: Money = Money::from_cents(200);
+
+/// Buys a `width` x `height` plot whose top-left corner is at world coordinate `at`.
+pub fn buy_land(
+    game_state: &mut MainGameState,
+    at: (i32, i32),
+    size: (u32, u32),
+    name: Option<String>,
+) -> Result<Money, String> {
+    let (width, height) = size;
+    if width == 0 || height == 0 {
+        return Err("A plot must be at least 1x1.".to_string());
+    }
+    if width > MAX_PLOT_SIDE || height > MAX_PLOT_SIDE {
+        return Err(format!("A plot can be at most {}x{}.", MAX_PLOT_SIDE, MAX_PLOT_SIDE));
+    }
+    let tiles = width.checked_mul(height).ok_or("That plot is too big.")?;
+    if at.0.checked_add(width as i32).is_none() || at.1.checked_add(height as i32).is_none() {
+        return Err("That land runs off the edge of the world.".to_string());
+    }
+    if let Some(existing) = game_state.plots.values().find(|plot| plot.overlaps(at.0, at.1, width, height)) {
+        return Err(format!("That land overlaps the plot at {}.", existing.label()));
+    }
+    if let Some(name) = &name {
+        if game_state.plots.values().any(|plot| plot.name.as_ref() == Some(name)) {
+            return Err(format!("A plot named '{}' already exists.", name));
+        }
+    }
+    let cost = LAND_PRICE_PER_TILE.times(tiles);
+    if game_state.wallet < cost {
+        return Err(format!("Not enough cash: the land costs ${}.", cost));
+    }
+
+    let item = format!("{}x{} plot at ({}, {})", width, height, at.0, at.1);
+    let tick = game_state.tick_counter;
+    game_state.ledger.pay(&mut game_state.wallet, tick, TransactionKind::Land, &item, tiles, cost);
+    let grid = create_grid(width, height, &mut game_state.rng);
+    game_state.plots.insert(at, Plot { x: at.0, y: at.1, name, grid });
+    Ok(cost)
+}
+
+pub fn list_land(game_state: &MainGameState) -> String {
+    let mut land_view = String::from("Plot\t\tSize\tPlanted\n");
+    for plot in game_state.plots.values() {
+        let planted = plot.grid.tiles.iter().flatten().filter(|tile| tile.plant.is_some()).count();
+        land_view.push_str(&format!(
+            "{}\t\t{}x{}\t{}\n",
+            plot.label(),
+            plot.width(),
+            plot.height(),
+            planted
+        ));
+    }
+    land_view
+}
+
+/// Applies `treatment` to the plant at (`x`, `y`), using one from the
+/// inventory if there is one and paying for it otherwise. Each application
+/// knocks back the pests and diseases it works against, less whatever
+/// resistance overuse has bred, and may leave residue or scorch the plant.
+pub fn apply_treatment(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32, treatment: Treatment) -> bool {
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
+        if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
+            let Some(plant) = &mut tile.plant else {
+                println!("There is no plant at ({}, {})", x, y);
+                return false;
+            };
+            // Sprays and released predators alike only go where they can work
+            let reaches_pest = |pest: &Pest| treatment.efficacy_against_pest(&pest.pest_type) > 0.0;
+            let reaches_disease = |disease: &Disease| treatment.efficacy_against_disease(disease.disease_type) > 0.0;
+            let has_target = tile.pest.as_ref().is_some_and(reaches_pest) || tile.disease.as_ref().is_some_and(reaches_disease);
+            if !has_target {
+                match &tile.disease {
+                    Some(disease) if disease.is_visible() && !disease.disease_type.profile().treatable => println!(
+                        "{:?} does not reach the {:?} at ({}, {}); clear the plant instead.",
+                        treatment, disease.disease_type, x, y
+                    ),
+                    _ => println!("Nothing at ({}, {}) that {:?} works against.", x, y, treatment),
+                }
+                return false;
+            }
+            let profile = treatment.profile();
+            let cost = Money::from_f32(profile.cost);
+            match game_state.inventory.get_mut(treatment.item_name()) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ if game_state.wallet < cost => {
+                    println!("Not enough cash to apply {:?}: it costs ${}.", treatment, cost);
+                    return false;
+                }
+                _ => game_state.ledger.pay(
+                    &mut game_state.wallet,
+                    game_state.tick_counter,
+                    TransactionKind::Expense,
+                    treatment.item_name(),
+                    1,
+                    cost,
+                ),
+            }
+
+            let resistance = game_state.treatment_resistance.get(&treatment).copied().unwrap_or(0.0);
+            if let Some(pest) = &mut tile.pest {
+                let efficacy = treatment.efficacy_against_pest(&pest.pest_type) * (1.0 - resistance);
+                pest.infestation_level *= 1.0 - efficacy;
+                if pest.infestation_level < TREATMENT_CLEAR_LEVEL {
+                    tile.pest = None;
+                }
+            }
+            if let Some(disease) = &mut tile.disease {
+                let efficacy = treatment.efficacy_against_disease(disease.disease_type) * (1.0 - resistance);
+                disease.severity *= 1.0 - efficacy;
+                if disease.severity < TREATMENT_CLEAR_LEVEL {
+                    tile.disease = None;
+                }
+            }
+
+            // Side effects
+            tile.beneficials.harm(profile.beneficial_harm);
+            if treatment == Treatment::Ladybugs {
+                // Released ladybugs stay on to hunt
+                tile.beneficials.ladybugs = 1.0;
+            }
+            tile.soil.residue = (tile.soil.residue + profile.residue).min(1.0);
+            if game_state.current_weather == Weather::Heatwave && profile.heat_scorch > 0.0 {
+                plant.health = (plant.health - profile.heat_scorch).max(0.0);
+                println!("The heat made the {:?} scorch the leaves at ({}, {}).", treatment, x, y);
+            }
 
-    economy::update_market_prices(&mut state.market);
+            println!("Applied {:?} to tile ({}, {})", treatment, x, y);
+            return true;
+        } else {
+            println!("Invalid coordinates: ({}, {})", x, y);
+        }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
+    }
+    false
 }
 
-pub fn apply_pesticide(game_state: &mut MainGameState, x: u32, y: u32) {
-    if let Some(plot) = game_state.plots.get_mut(&(0, 0)) {
+/// Applies `treatment` to every tile of `plot` matched by `selection`. The
+/// pests and diseases build resistance once per spraying, however many tiles
+/// it covered.
+pub fn treat_selection(
+    game_state: &mut MainGameState,
+    plot: (i32, i32),
+    selection: &TileSelection,
+    treatment: Treatment,
+) -> BatchSummary {
+    let summary = apply_to_selection(game_state, plot, selection, |state, plot, x, y| {
+        apply_treatment(state, plot, x, y, treatment)
+    });
+    if summary.applied > 0 && treatment.profile().builds_resistance {
+        let level = game_state.treatment_resistance.entry(treatment).or_insert(0.0);
+        *level = (*level + RESISTANCE_BUILDUP).min(MAX_RESISTANCE);
+    }
+    summary
+}
+
+/// Starts working `kind` into the soil at (`x`, `y`); the soil type shifts after `AMENDMENT_TICKS`.
+pub fn amend_soil(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32, kind: AmendmentKind) -> bool {
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
         if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
-            if tile.pest.is_some() {
-                tile.pest = None;
-                println!("Applied pesticide to tile ({}, {})", x, y);
+            if tile.soil.amendment.is_some() {
+                println!("Tile ({}, {}) is already being amended.", x, y);
+            } else if kind.apply_to(tile.soil.soil_type) == tile.soil.soil_type {
+                println!("Adding {:?} would not change the {:?} at ({}, {}).", kind, tile.soil.soil_type, x, y);
+            } else if game_state.wallet < AMENDMENT_COST {
+                println!("Not enough cash to amend soil: it costs ${}.", AMENDMENT_COST);
             } else {
-                println!("No pest to remove at ({}, {})", x, y);
+                game_state.ledger.pay(
+                    &mut game_state.wallet,
+                    game_state.tick_counter,
+                    TransactionKind::Expense,
+                    &format!("{:?} amendment", kind),
+                    1,
+                    AMENDMENT_COST,
+                );
+                tile.soil.amendment = Some(Amendment {
+                    kind,
+                    ticks_remaining: AMENDMENT_TICKS,
+                });
+                println!(
+                    "Working {:?} into ({}, {}); it will become {:?} in {} ticks.",
+                    kind,
+                    x,
+                    y,
+                    kind.apply_to(tile.soil.soil_type),
+                    AMENDMENT_TICKS
+                );
+                return true;
             }
         } else {
             println!("Invalid coordinates: ({}, {})", x, y);
         }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
+    }
+    false
+}
+
+/// Spreads lime (`raise`) or sulfur on (`x`, `y`), shifting its pH by
+/// `PH_AMENDMENT_SHIFT` over the next few ticks.
+pub fn adjust_ph(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32, raise: bool) -> bool {
+    let (material, shift) = if raise { ("lime", PH_AMENDMENT_SHIFT) } else { ("sulfur", -PH_AMENDMENT_SHIFT) };
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
+        if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
+            if game_state.wallet < AMENDMENT_COST {
+                println!("Not enough cash to spread {}: it costs ${}.", material, AMENDMENT_COST);
+                return false;
+            }
+            let tick = game_state.tick_counter;
+            game_state.ledger.pay(&mut game_state.wallet, tick, TransactionKind::Expense, material, 1, AMENDMENT_COST);
+            tile.soil.pending_ph_shift += shift;
+            println!(
+                "Spread {} on ({}, {}). pH {:.2} is heading for {:.2}.",
+                material,
+                x,
+                y,
+                tile.soil.soil_ph,
+                (tile.soil.soil_ph + tile.soil.pending_ph_shift).clamp(MIN_SOIL_PH, MAX_SOIL_PH)
+            );
+            return true;
+        } else {
+            println!("Invalid coordinates: ({}, {})", x, y);
+        }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
+    }
+    false
+}
+
+/// What hoeing out one tile's weeds costs.
+pub const WEEDING_COST: Money = Money::from_cents(5);
+/// What mulching one tile costs.
+pub const MULCH_COST: Money = Money::from_cents(50);
+/// How many ticks a layer of mulch lasts.
+pub const MULCH_TICKS: u32 = 20;
+
+/// Hoes the weeds out of (`x`, `y`), optionally laying mulch to keep them down.
+pub fn weed_tile(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32, mulch: bool) -> bool {
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
+        if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
+            if tile.soil.weeds == 0.0 && !mulch {
+                println!("There are no weeds at ({}, {})", x, y);
+                return false;
+            }
+            let (cost, item) = if mulch { (WEEDING_COST + MULCH_COST, "Weeding and mulch") } else { (WEEDING_COST, "Weeding") };
+            if game_state.wallet < cost {
+                println!("Not enough cash to weed: it costs ${}.", cost);
+                return false;
+            }
+            let tick = game_state.tick_counter;
+            game_state.ledger.pay(&mut game_state.wallet, tick, TransactionKind::Expense, item, 1, cost);
+            tile.soil.weeds = 0.0;
+            if mulch {
+                tile.soil.mulch = MULCH_TICKS;
+                println!("Weeded and mulched tile ({}, {})", x, y);
+            } else {
+                println!("Weeded tile ({}, {})", x, y);
+            }
+            return true;
+        } else {
+            println!("Invalid coordinates: ({}, {})", x, y);
+        }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
+    }
+    false
+}
+
+/// Describes the soil and any plant at (`x`, `y`), including nutrient deficiencies.
+pub fn inspect_tile(game_state: &MainGameState, plot: (i32, i32), x: u32, y: u32) -> Option<String> {
+    let plot = game_state.plots.get(&plot)?;
+    let tile = plot.grid.tiles.get(y as usize)?.get(x as usize)?;
+    let season = Season::from_tick(game_state.tick_counter);
+    let light = light::light_levels(plot, game_state.current_weather, season)[y as usize][x as usize];
+    let soil = &tile.soil;
+    let mut report = format!(
+        "({}, {}) {:?} | moisture {:.2} | N {:.2} P {:.2} K {:.2} | pH {:.2} | weeds {:.2} | light {:.1}h",
+        x,
+        y,
+        soil.soil_type,
+        soil.soil_moisture,
+        soil.soil_nutrients.nitrogen,
+        soil.soil_nutrients.phosphorus,
+        soil.soil_nutrients.potassium,
+        soil.soil_ph,
+        soil.weeds,
+        light
+    );
+    if let Some(plant) = &tile.plant {
+        report.push_str(&format!(
+            "\n    {} #{} (gen {}): {:?}, age {}, {:.1}m tall, health {:.2}, needs {:.1}h light, deficiency: {}, pollinated {:.0}%",
+            plant.species,
+            plant.id,
+            plant.generation,
+            plant.life_cycle_stage,
+            plant.age,
+            plant.current_height(),
+            plant.health,
+            plant.genetics.light_req,
+            plant.deficiency.describe(),
+            plant.pollination * 100.0
+        ));
+    }
+    if let Some(pest) = &tile.pest {
+        report.push_str(&format!("\n    pest: {:?} ({:.2})", pest.pest_type, pest.infestation_level));
+    }
+    let beneficials = tile.beneficials.describe();
+    if beneficials != "none" {
+        report.push_str(&format!("\n    beneficials: {}", beneficials));
+    }
+    if let Some(disease) = tile.disease.as_ref().filter(|disease| disease.is_visible()) {
+        report.push_str(&format!(
+            "\n    disease: {:?} ({:.2}), {}",
+            disease.disease_type,
+            disease.severity,
+            disease.disease_type.symptoms()
+        ));
+    }
+    if soil.residue > 0.0 {
+        report.push_str(&format!("\n    treatment residue {:.2}", soil.residue));
+    }
+    if let Some(contamination) = &soil.contamination {
+        report.push_str(&format!(
+            "\n    soil contaminated with {:?} for {} more tick(s)",
+            contamination.disease_type, contamination.ticks_remaining
+        ));
+    }
+    Some(report)
+}
+
+/// Removes a dead or visibly diseased plant so the tile can be planted
+/// again. Soil-borne diseases stay behind in the soil.
+pub fn clear_tile(game_state: &mut MainGameState, plot: (i32, i32), x: u32, y: u32) -> bool {
+    if let Some(plot) = game_state.plots.get_mut(&plot) {
+        if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
+            let diseased = tile.disease.as_ref().is_some_and(Disease::is_visible);
+            match &tile.plant {
+                Some(plant) if plant.life_cycle_stage == plant::LifeCycleStage::Dead || diseased => {
+                    let condition = if diseased { "diseased" } else { "dead" };
+                    println!("Cleared the {} {} from ({}, {})", condition, plant.species, x, y);
+                    tile.plant = None;
+                    tile.pest = None;
+                    tile.disease = None;
+                    return true;
+                }
+                Some(_) => println!("The plant at ({}, {}) is still alive.", x, y),
+                None => println!("There is no plant at ({}, {})", x, y),
+            }
+        } else {
+            println!("Invalid coordinates: ({}, {})", x, y);
+        }
+    } else {
+        println!("No plot at ({}, {})", plot.0, plot.1);
+    }
+    false
+}
+
+/// The outcome of fanning one tile command out over a `TileSelection`.
+pub struct BatchSummary {
+    pub targeted: u32,
+    pub applied: u32,
+    pub cost: Money,
+}
+
+impl BatchSummary {
+    pub fn report(&self, verb: &str) -> String {
+        format!(
+            "{} {} of {} tile(s) ({} skipped) for ${}.",
+            verb,
+            self.applied,
+            self.targeted,
+            self.targeted - self.applied,
+            self.cost
+        )
+    }
+}
+
+/// Runs `action` on every tile of `plot` matched by `selection`, tallying
+/// successes and the money spent.
+pub fn apply_to_selection(
+    game_state: &mut MainGameState,
+    plot: (i32, i32),
+    selection: &TileSelection,
+    mut action: impl FnMut(&mut MainGameState, (i32, i32), u32, u32) -> bool,
+) -> BatchSummary {
+    // A lone tile goes straight to the action so out-of-range coordinates are still reported.
+    let tiles = match game_state.plots.get(&plot) {
+        Some(_) if selection.is_single() => vec![(selection.xs.start, selection.ys.start)],
+        Some(target) => selection.tiles(target),
+        None => Vec::new(),
+    };
+    let wallet_before = game_state.wallet;
+    let mut applied = 0;
+    for &(x, y) in &tiles {
+        if action(game_state, plot, x, y) {
+            applied += 1;
+        }
+    }
+    BatchSummary {
+        targeted: tiles.len() as u32,
+        applied,
+        cost: wallet_before - game_state.wallet,
     }
 }
 
 pub fn forecast(game_state: &MainGameState, ticks: u64) {
-    let mut rng = rand::thread_rng();
-    let mut weather;
+    // Roll on a copy so that peeking at the forecast never perturbs the game's RNG stream.
+    let mut rng = game_state.rng.clone();
     println!("Weather forecast:");
     for i in 0..ticks {
-        let next_weather = [
-            Weather::Sunny,
-            Weather::Cloudy,
-            Weather::Rainy,
-            Weather::Heatwave,
-        ]
-        .choose(&mut rng)
-        .unwrap();
-        weather = *next_weather;
+        let weather = roll_weather(&mut rng);
         println!("Tick {}: {:?}", game_state.tick_counter + i + 1, weather);
     }
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[derive(Serialize, Deserialize)]
 pub struct Soil {
@@ -8,47 +8,130 @@ pub struct Soil {
     pub soil_nutrients: Nutrients,
     pub soil_ph: f32,
     pub weeds: f32,
+    #[serde(default)]
+    pub amendment: Option<Amendment>,
+    /// Lime (positive) or sulfur (negative) still to take effect on `soil_ph`.
+    #[serde(default)]
+    pub pending_ph_shift: f32,
+    /// Ticks of weed-suppressing mulch left on the tile.
+    #[serde(default)]
+    pub mulch: u32,
+    /// A soil-borne disease left behind by an earlier plant.
+    #[serde(default)]
+    pub contamination: Option<Contamination>,
+    /// Chemical residue left by treatments, from 0.0 to 1.0.
+    #[serde(default)]
+    pub residue: f32,
 }
 
-// TODO: Implement different soil types with distinct properties.
-//
-// Design Idea:
-// - Each `SoilType` variant could have associated data representing its properties.
-//   For example:
-//   - Water retention: How quickly the soil dries out or becomes waterlogged.
-//   - Nutrient retention: How well the soil holds onto nutrients.
-//   - Drainage: How quickly water passes through the soil.
-//   - Aeration: How much air is in the soil.
-//
-// - These properties would then influence:
-//   - Plant growth rates.
-//   - The effectiveness of watering and fertilizing.
-//   - The likelihood of certain events, like root rot or drought stress.
-//
-// Example implementation:
-//
-// pub enum SoilType {
-//     Sand { water_retention: f32, nutrient_retention: f32 },
-//     Clay { water_retention: f32, nutrient_retention: f32 },
-//     Loam { water_retention: f32, nutrient_retention: f32 },
-// }
-//
-// The `process_environment` function in `engine.rs` would then use these
-// properties to calculate changes in soil moisture and nutrient levels.
-
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub enum SoilType {
     Sand,
     Clay,
     Loam,
 }
 
+/// How a soil type handles water, nutrients and air. Each property runs
+/// from 0.0 to 1.0, and loam sits at 0.5 across the board so that it
+/// behaves as the neutral baseline.
+pub struct SoilProperties {
+    /// How well the soil resists drying out in sun and heat.
+    pub water_retention: f32,
+    /// How quickly water above field capacity passes through.
+    pub drainage: f32,
+    /// How well the soil holds fertilizer instead of leaching it away.
+    pub nutrient_retention: f32,
+    /// How much air reaches the roots, which matters most when waterlogged.
+    pub aeration: f32,
+}
+
+/// Moisture above which water starts to drain away and roots start to drown.
+pub const FIELD_CAPACITY: f32 = 0.7;
+
+impl SoilType {
+    pub fn properties(&self) -> SoilProperties {
+        match self {
+            SoilType::Sand => SoilProperties {
+                water_retention: 0.25,
+                drainage: 0.9,
+                nutrient_retention: 0.2,
+                aeration: 0.9,
+            },
+            SoilType::Clay => SoilProperties {
+                water_retention: 0.8,
+                drainage: 0.15,
+                nutrient_retention: 0.85,
+                aeration: 0.2,
+            },
+            SoilType::Loam => SoilProperties {
+                water_retention: 0.5,
+                drainage: 0.5,
+                nutrient_retention: 0.5,
+                aeration: 0.5,
+            },
+        }
+    }
+}
+
+impl SoilProperties {
+    /// Multiplier on moisture lost to evaporation (sand 1.5, loam 1.0, clay 0.4).
+    pub fn evaporation_factor(&self) -> f32 {
+        2.0 * (1.0 - self.water_retention)
+    }
+
+    /// Share of rain or watering that soaks in rather than running off.
+    pub fn absorption_factor(&self) -> f32 {
+        (0.5 + self.drainage).min(1.0)
+    }
+
+    /// Share of applied fertilizer the soil holds on to.
+    pub fn fertilizer_factor(&self) -> f32 {
+        (0.5 + self.nutrient_retention).min(1.0)
+    }
+
+    /// Growth multiplier for plants in waterlogged soil.
+    pub fn waterlogged_growth_factor(&self) -> f32 {
+        (0.5 + self.aeration).min(1.0)
+    }
+}
+
+/// Material worked into a tile to shift its soil type along the
+/// sand - loam - clay scale.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum AmendmentKind {
+    Sand,
+    Compost,
+    Clay,
+}
+
+impl AmendmentKind {
+    /// The soil type `soil_type` becomes once this amendment has been fully worked in.
+    pub fn apply_to(&self, soil_type: SoilType) -> SoilType {
+        match (self, soil_type) {
+            (AmendmentKind::Sand, SoilType::Clay) => SoilType::Loam,
+            (AmendmentKind::Sand, _) => SoilType::Sand,
+            (AmendmentKind::Clay, SoilType::Sand) => SoilType::Loam,
+            (AmendmentKind::Clay, _) => SoilType::Clay,
+            (AmendmentKind::Compost, _) => SoilType::Loam,
+        }
+    }
+}
+
+/// An amendment that is still being worked into the soil.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Amendment {
+    pub kind: AmendmentKind,
+    pub ticks_remaining: u32,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Nutrients {
     pub nitrogen: f32,
     pub phosphorus: f32,
     pub potassium: f32,
 }
+use crate::beneficials::Beneficials;
+use crate::disease::{Contamination, Disease};
 use crate::pests::Pest;
 use crate::plant;
 #[derive(Serialize, Deserialize)]
@@ -56,6 +139,10 @@ pub struct Tile {
     pub soil: Soil,
     pub plant: Option<plant::Plant>,
     pub pest: Option<Pest>,
+    #[serde(default)]
+    pub disease: Option<Disease>,
+    #[serde(default)]
+    pub beneficials: Beneficials,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -67,37 +154,180 @@ pub struct Grid {
 pub struct Plot {
     pub x: i32,
     pub y: i32,
+    #[serde(default)]
+    pub name: Option<String>,
     pub grid: Grid,
 }
 
+impl Plot {
+    pub fn width(&self) -> u32 {
+        self.grid.tiles.first().map_or(0, |row| row.len() as u32)
+    }
+
+    pub fn height(&self) -> u32 {
+        self.grid.tiles.len() as u32
+    }
+
+    /// Whether this plot covers any world cell of the `width` x `height` rectangle at (`x`, `y`).
+    pub fn overlaps(&self, x: i32, y: i32, width: u32, height: u32) -> bool {
+        // Widened so that plots near the edge of the world cannot overflow
+        let (x, y) = (x as i64, y as i64);
+        let (own_x, own_y) = (self.x as i64, self.y as i64);
+        x < own_x + self.width() as i64
+            && own_x < x + width as i64
+            && y < own_y + self.height() as i64
+            && own_y < y + height as i64
+    }
+
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => format!("{} ({},{})", name, self.x, self.y),
+            None => format!("({},{})", self.x, self.y),
+        }
+    }
+}
+
+/// The plot every new game starts with, used when a command has no `--plot`.
+pub const HOME_PLOT: (i32, i32) = (0, 0);
+/// The longest side a plot of land can have.
+pub const MAX_PLOT_SIDE: u32 = 1000;
+
+/// Finds a plot by its "X,Y" coordinate or by its name.
+pub fn resolve_plot(state: &MainGameState, selector: Option<&str>) -> Result<(i32, i32), String> {
+    let key = match selector {
+        None => HOME_PLOT,
+        Some(selector) => match parse_coordinate(selector) {
+            Some(key) => key,
+            None => state
+                .plots
+                .iter()
+                .find(|(_, plot)| plot.name.as_deref() == Some(selector))
+                .map(|(key, _)| *key)
+                .ok_or_else(|| format!("No plot named '{}'", selector))?,
+        },
+    };
+    if state.plots.contains_key(&key) {
+        Ok(key)
+    } else {
+        Err(format!("No plot at ({}, {})", key.0, key.1))
+    }
+}
+
+/// Parses an "X,Y" world coordinate.
+pub fn parse_coordinate(value: &str) -> Option<(i32, i32)> {
+    let (x, y) = value.split_once(',')?;
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
 use crate::economy::Market;
+use crate::rng::GameRng;
 use crate::events::GameEvent;
+use crate::breeding::Hybrid;
+use crate::lineage::Ancestor;
+use crate::orders::Order;
+use crate::seeds::SeedLot;
+use crate::treatment::Treatment;
 use crate::weather::Weather;
+use crate::ledger::{FeeSchedule, Ledger};
+use crate::money::Money;
+use crate::catalogue::SpeciesDefinition;
 
 #[serde_with::serde_as]
 #[derive(Serialize, Deserialize)]
 pub struct MainGameState {
     #[serde_as(as = "Vec<(_, _)>")]
-    pub plots: HashMap<(i32, i32), Plot>,
+    pub plots: BTreeMap<(i32, i32), Plot>,
     pub tick_counter: u64,
-    pub inventory: HashMap<String, u32>,
-    pub wallet: f32,
+    pub inventory: BTreeMap<String, u32>,
+    pub wallet: Money,
     pub market: Market,
     pub current_weather: Weather,
     pub events: Vec<GameEvent>,
+    /// How resistant the garden's pests and diseases have grown to each treatment.
+    #[serde(default)]
+    pub treatment_resistance: BTreeMap<Treatment, f32>,
+    /// Bought seed of each species, ready to plant. Collected and bred seed
+    /// is kept in `seed_lots` instead, as each lot carries its own genome.
+    #[serde(default)]
+    pub seed_inventory: BTreeMap<String, u32>,
+    #[serde(default)]
+    pub seed_lots: BTreeMap<u32, SeedLot>,
+    /// Species bred by crossing two others, by name.
+    #[serde(default)]
+    pub hybrids: BTreeMap<String, Hybrid>,
+    /// Every plant and seed lot there has been, by id.
+    #[serde(default)]
+    pub pedigree: BTreeMap<u32, Ancestor>,
+    /// The id the next plant or seed lot will get. Both share one sequence
+    /// so that a pedigree id is never ambiguous.
+    #[serde(default = "first_id", alias = "next_seed_lot_id")]
+    pub next_id: u32,
+    /// Standing market orders waiting for their price, by id.
+    #[serde(default)]
+    pub orders: BTreeMap<u32, Order>,
+    #[serde(default = "first_id")]
+    pub next_order_id: u32,
+    /// Every transaction since the game began.
+    #[serde(default)]
+    pub ledger: Ledger,
+    #[serde(default)]
+    pub fees: FeeSchedule,
+    /// The species catalogue the game is played with, when it is not the
+    /// built-in one, so that loading the save brings it back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catalogue: Option<Vec<SpeciesDefinition>>,
+    #[serde(default = "crate::rng::from_entropy")]
+    pub rng: GameRng,
+}
+
+fn first_id() -> u32 {
+    1
 }
 
 use rand::Rng;
 
-pub fn create_grid(width: u32, height: u32) -> Grid {
+/// Side of the square cell each patch of uniform soil is centred in when a grid is generated.
+const SOIL_PATCH_SIDE: u32 = 4;
+
+pub fn create_grid(width: u32, height: u32, rng: &mut impl Rng) -> Grid {
+    // Scatter one patch centre with a random soil type in each cell of a
+    // coarse grid; every tile takes the soil of the nearest centre in its own
+    // or a neighbouring cell, giving contiguous mixed patches.
+    let cells_x = width.div_ceil(SOIL_PATCH_SIDE).max(1);
+    let cells_y = height.div_ceil(SOIL_PATCH_SIDE).max(1);
+    let patches: Vec<Vec<(u32, u32, SoilType)>> = (0..cells_y)
+        .map(|cell_y| {
+            (0..cells_x)
+                .map(|cell_x| {
+                    let soil_type = match rng.gen_range(0..4) {
+                        0 => SoilType::Sand,
+                        1 => SoilType::Clay,
+                        _ => SoilType::Loam,
+                    };
+                    let x = cell_x * SOIL_PATCH_SIDE + rng.gen_range(0..SOIL_PATCH_SIDE);
+                    let y = cell_y * SOIL_PATCH_SIDE + rng.gen_range(0..SOIL_PATCH_SIDE);
+                    (x, y, soil_type)
+                })
+                .collect()
+        })
+        .collect();
+
     let mut tiles = Vec::new();
-    let mut rng = rand::thread_rng();
-    for _ in 0..height {
+    for y in 0..height {
         let mut row = Vec::new();
-        for _ in 0..width {
+        for x in 0..width {
+            let (cell_x, cell_y) = (x / SOIL_PATCH_SIDE, y / SOIL_PATCH_SIDE);
+            let soil_type = (cell_y.saturating_sub(1)..=(cell_y + 1).min(cells_y - 1))
+                .flat_map(|row| {
+                    let row = &patches[row as usize];
+                    let columns = cell_x.saturating_sub(1)..=(cell_x + 1).min(cells_x - 1);
+                    columns.map(move |column| &row[column as usize])
+                })
+                .min_by_key(|(px, py, _)| px.abs_diff(x).pow(2) + py.abs_diff(y).pow(2))
+                .map_or(SoilType::Loam, |(_, _, soil_type)| *soil_type);
             row.push(Tile {
                 soil: Soil {
-                    soil_type: SoilType::Loam,
+                    soil_type,
                     soil_moisture: rng.gen_range(0.3..0.7),
                     soil_nutrients: Nutrients {
                         nitrogen: rng.gen_range(0.3..0.7),
@@ -106,9 +336,16 @@ pub fn create_grid(width: u32, height: u32) -> Grid {
                     },
                     soil_ph: rng.gen_range(6.0..7.5),
                     weeds: 0.0,
+                    amendment: None,
+                    pending_ph_shift: 0.0,
+                    mulch: 0,
+                    contamination: None,
+                    residue: 0.0,
                 },
                 plant: None,
                 pest: None,
+                disease: None,
+                beneficials: Beneficials::default(),
             });
         }
         tiles.push(row);
@@ -0,0 +1,259 @@
+// src/genome.rs
+
+use crate::plant::PlantGenetics;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dominance {
+    Dominant,
+    Recessive,
+}
+
+/// One copy of a gene, carrying the trait value it codes for.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Allele {
+    pub value: f32,
+    pub dominance: Dominance,
+}
+
+/// The two copies of a gene a plant carries, one from each parent.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GenePair(pub Allele, pub Allele);
+
+impl GenePair {
+    /// Both copies the same allele.
+    pub fn homozygous(value: f32) -> GenePair {
+        let allele = Allele { value, dominance: Dominance::Dominant };
+        GenePair(allele, allele)
+    }
+
+    /// The value the pair expresses: a dominant allele masks a recessive
+    /// one, and two alleles of equal dominance blend.
+    pub fn express(&self) -> f32 {
+        match (self.0.dominance, self.1.dominance) {
+            (Dominance::Dominant, Dominance::Recessive) => self.0.value,
+            (Dominance::Recessive, Dominance::Dominant) => self.1.value,
+            _ => (self.0.value + self.1.value) / 2.0,
+        }
+    }
+}
+
+/// The genes that together determine a plant's `PlantGenetics`. Most traits
+/// are set by a single gene pair; see `Gene::loci` for those that are not.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Gene {
+    GrowthTime,
+    YieldMin,
+    YieldMax,
+    MoistureMin,
+    MoistureMax,
+    NitrogenUse,
+    PhosphorusUse,
+    PotassiumUse,
+    LightReq,
+    PestResistance,
+    DiseaseResistance,
+    GeneticStability,
+    PhLow,
+    PhTolerance,
+    Height,
+}
+
+impl Gene {
+    pub const ALL: [Gene; 15] = [
+        Gene::GrowthTime,
+        Gene::YieldMin,
+        Gene::YieldMax,
+        Gene::MoistureMin,
+        Gene::MoistureMax,
+        Gene::NitrogenUse,
+        Gene::PhosphorusUse,
+        Gene::PotassiumUse,
+        Gene::LightReq,
+        Gene::PestResistance,
+        Gene::DiseaseResistance,
+        Gene::GeneticStability,
+        Gene::PhLow,
+        Gene::PhTolerance,
+        Gene::Height,
+    ];
+
+    /// How many gene pairs together set this trait. Yield and the resistances
+    /// are polygenic: their pairs are averaged, so they vary by degrees
+    /// rather than flipping with a single dominant allele.
+    pub fn loci(&self) -> usize {
+        match self {
+            Gene::YieldMin | Gene::YieldMax | Gene::PestResistance | Gene::DiseaseResistance => POLYGENIC_LOCI,
+            _ => 1,
+        }
+    }
+
+    /// The value of this gene in a plant with `genetics`.
+    fn value_in(&self, genetics: &PlantGenetics) -> f32 {
+        match self {
+            Gene::GrowthTime => genetics.growth_time as f32,
+            Gene::YieldMin => genetics.yield_range.0 as f32,
+            Gene::YieldMax => genetics.yield_range.1 as f32,
+            Gene::MoistureMin => genetics.ideal_moisture_range.0,
+            Gene::MoistureMax => genetics.ideal_moisture_range.1,
+            Gene::NitrogenUse => genetics.nutrient_consumption.0,
+            Gene::PhosphorusUse => genetics.nutrient_consumption.1,
+            Gene::PotassiumUse => genetics.nutrient_consumption.2,
+            Gene::LightReq => genetics.light_req,
+            Gene::PestResistance => genetics.pest_resistance,
+            Gene::DiseaseResistance => genetics.disease_resistance,
+            Gene::GeneticStability => genetics.genetic_stability,
+            Gene::PhLow => genetics.preferred_ph_range.0,
+            Gene::PhTolerance => genetics.preferred_ph_range.1 - genetics.preferred_ph_range.0,
+            Gene::Height => genetics.height,
+        }
+    }
+}
+
+/// Gene pairs behind each polygenic trait.
+pub const POLYGENIC_LOCI: usize = 4;
+/// How far, as a share of the species value, an individual's alleles may stray.
+pub const ALLELE_VARIATION: f32 = 0.1;
+/// How far, as a share of its value, a mutation can shift an allele.
+pub const MUTATION_SIZE: f32 = 0.2;
+/// Share of mutations that flip an allele's dominance rather than its value.
+pub const DOMINANCE_FLIP_SHARE: f64 = 0.25;
+
+/// A plant's full set of gene pairs.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Genome {
+    pub genes: BTreeMap<Gene, GenePair>,
+    /// The further gene pairs of polygenic traits, which count equally with
+    /// the one in `genes`. Empty in saves from before traits were polygenic,
+    /// whose traits then rest on the single pair.
+    #[serde(default)]
+    pub minor_genes: BTreeMap<Gene, Vec<GenePair>>,
+}
+
+impl Genome {
+    /// A genome that is homozygous for every value in `genetics`, so that it
+    /// expresses exactly those genetics. Used for plants saved before
+    /// genomes existed.
+    pub fn uniform(genetics: &PlantGenetics) -> Genome {
+        Genome::from_pairs(|gene| GenePair::homozygous(gene.value_in(genetics)))
+    }
+
+    /// A genome with `pair(gene)` at every locus of every gene.
+    fn from_pairs(mut pair: impl FnMut(Gene) -> GenePair) -> Genome {
+        let mut genome = Genome::default();
+        for gene in Gene::ALL {
+            genome.genes.insert(gene, pair(gene));
+            if gene.loci() > 1 {
+                genome.minor_genes.insert(gene, (1..gene.loci()).map(|_| pair(gene)).collect());
+            }
+        }
+        genome
+    }
+
+    /// A new individual of a species with `genetics`: each allele lands
+    /// within `ALLELE_VARIATION` of the species value and is dominant or
+    /// recessive at random.
+    pub fn individual(genetics: &PlantGenetics, rng: &mut impl Rng) -> Genome {
+        let mut allele = |value: f32| Allele {
+            value: value * (1.0 + rng.gen_range(-ALLELE_VARIATION..=ALLELE_VARIATION)),
+            dominance: if rng.gen_bool(0.5) { Dominance::Dominant } else { Dominance::Recessive },
+        };
+        Genome::from_pairs(|gene| {
+            let value = gene.value_in(genetics);
+            GenePair(allele(value), allele(value))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.genes.is_empty()
+    }
+
+    /// The value `gene` expresses, the mean over all its pairs, falling back
+    /// to `default` if the genome lacks it.
+    fn expressed(&self, gene: Gene, default: f32) -> f32 {
+        let Some(pair) = self.genes.get(&gene) else { return default };
+        let minor = self.minor_genes.get(&gene).map_or(&[][..], Vec::as_slice);
+        let total: f32 = std::iter::once(pair).chain(minor).map(GenePair::express).sum();
+        total / (1 + minor.len()) as f32
+    }
+
+    /// The traits this genome gives a plant. `fallback` supplies any gene
+    /// the genome does not carry.
+    pub fn express(&self, fallback: &PlantGenetics) -> PlantGenetics {
+        let trait_value = |gene: Gene| self.expressed(gene, gene.value_in(fallback));
+        let yield_min = trait_value(Gene::YieldMin).round().max(0.0) as u32;
+        let yield_max = (trait_value(Gene::YieldMax).round().max(0.0) as u32).max(yield_min);
+        let moisture_min = trait_value(Gene::MoistureMin).clamp(0.0, 1.0);
+        let moisture_max = trait_value(Gene::MoistureMax).clamp(moisture_min, 1.0);
+        let ph_low = trait_value(Gene::PhLow);
+        PlantGenetics {
+            growth_time: trait_value(Gene::GrowthTime).round().max(1.0) as u32,
+            yield_range: (yield_min, yield_max),
+            ideal_moisture_range: (moisture_min, moisture_max),
+            nutrient_consumption: (
+                trait_value(Gene::NitrogenUse).max(0.0),
+                trait_value(Gene::PhosphorusUse).max(0.0),
+                trait_value(Gene::PotassiumUse).max(0.0),
+            ),
+            light_req: trait_value(Gene::LightReq).max(0.0),
+            pest_resistance: trait_value(Gene::PestResistance).clamp(0.0, 1.0),
+            disease_resistance: trait_value(Gene::DiseaseResistance).clamp(0.0, 1.0),
+            genetic_stability: trait_value(Gene::GeneticStability).clamp(0.0, 1.0),
+            preferred_ph_range: (ph_low, ph_low + trait_value(Gene::PhTolerance).max(0.0)),
+            height: trait_value(Gene::Height).max(0.0),
+        }
+    }
+
+    /// Offspring of `mother` and `father`: for every gene pair, one allele
+    /// from each parent's, picked at random. Crossing a plant with itself
+    /// gives a self-pollinated seed.
+    pub fn cross(mother: &Genome, father: &Genome, rng: &mut impl Rng) -> Genome {
+        let mut pick = |mother_pair: &GenePair, father_pair: &GenePair| {
+            let from_mother = if rng.gen_bool(0.5) { mother_pair.0 } else { mother_pair.1 };
+            let from_father = if rng.gen_bool(0.5) { father_pair.0 } else { father_pair.1 };
+            GenePair(from_mother, from_father)
+        };
+        let genes = mother
+            .genes
+            .iter()
+            .filter_map(|(gene, mother_pair)| Some((*gene, pick(mother_pair, father.genes.get(gene)?))))
+            .collect();
+        // A parent from an old save has no minor genes; its child keeps only the pairs both carry
+        let minor_genes = mother
+            .minor_genes
+            .iter()
+            .filter_map(|(gene, mother_pairs)| {
+                let father_pairs = father.minor_genes.get(gene)?;
+                Some((*gene, mother_pairs.iter().zip(father_pairs).map(|(m, f)| pick(m, f)).collect()))
+            })
+            .collect();
+        Genome { genes, minor_genes }
+    }
+
+    /// Gives each allele a `1 - stability` chance to mutate, either shifting
+    /// its value by up to `MUTATION_SIZE` or flipping its dominance.
+    /// Returns how many alleles mutated.
+    pub fn mutate(&mut self, stability: f32, rng: &mut impl Rng) -> u32 {
+        let chance = (1.0 - stability).clamp(0.0, 1.0) as f64;
+        let mut mutations = 0;
+        for pair in self.genes.values_mut().chain(self.minor_genes.values_mut().flatten()) {
+            for allele in [&mut pair.0, &mut pair.1] {
+                if !rng.gen_bool(chance) {
+                    continue;
+                }
+                mutations += 1;
+                if rng.gen_bool(DOMINANCE_FLIP_SHARE) {
+                    allele.dominance = match allele.dominance {
+                        Dominance::Dominant => Dominance::Recessive,
+                        Dominance::Recessive => Dominance::Dominant,
+                    };
+                } else {
+                    allele.value *= 1.0 + rng.gen_range(-MUTATION_SIZE..=MUTATION_SIZE);
+                }
+            }
+        }
+        mutations
+    }
+}
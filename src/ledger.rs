@@ -0,0 +1,229 @@
+// src/ledger.rs
+
+use crate::economy;
+use crate::money::Money;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionKind {
+    /// Produce sold on the market.
+    Sale,
+    /// Seed or supplies bought on the market.
+    Purchase,
+    /// Money spent working the garden: water, fertilizer, treatments and the like.
+    Expense,
+    Land,
+    Tax,
+}
+
+/// One movement of money in or out of the wallet.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Transaction {
+    pub tick: u64,
+    pub kind: TransactionKind,
+    pub item: String,
+    pub quantity: u32,
+    /// The average price per unit before fees.
+    pub unit_price: Money,
+    pub fees: Money,
+    /// The change to the wallet, fees included: positive for money in.
+    pub net: Money,
+}
+
+/// The highest land tax per tile `FeeSchedule::update` accepts.
+pub const MAX_LAND_TAX_PER_TILE: Money = Money::from_cents(10_000);
+
+/// Transaction fees and land tax.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FeeSchedule {
+    /// Share of the value of every market trade charged as a fee.
+    pub transaction_rate: f32,
+    /// Tax charged per tile of owned land each tax period.
+    pub land_tax_per_tile: Money,
+    /// Ticks between land tax bills; 0 turns the tax off.
+    pub tax_period: u64,
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        FeeSchedule {
+            transaction_rate: 0.02,
+            land_tax_per_tile: Money::from_cents(2),
+            tax_period: 30,
+        }
+    }
+}
+
+impl FeeSchedule {
+    /// Changes whichever of the fees are given, or none if any is out of
+    /// range. The land tax is given in dollars per tile.
+    pub fn update(
+        &mut self,
+        transaction_rate: Option<f32>,
+        land_tax_per_tile: Option<f32>,
+        tax_period: Option<u64>,
+    ) -> Result<(), String> {
+        if transaction_rate.is_some_and(|rate| !(0.0..1.0).contains(&rate)) {
+            return Err("the transaction rate must be at least 0 and below 1".to_string());
+        }
+        let out_of_range = |tax: f32| !tax.is_finite() || tax < 0.0 || Money::from_f32(tax) > MAX_LAND_TAX_PER_TILE;
+        if land_tax_per_tile.is_some_and(out_of_range) {
+            return Err(format!("the land tax must be between 0 and {} per tile", MAX_LAND_TAX_PER_TILE));
+        }
+        let land_tax_per_tile = land_tax_per_tile.map(Money::from_f32);
+        self.transaction_rate = transaction_rate.unwrap_or(self.transaction_rate);
+        self.land_tax_per_tile = land_tax_per_tile.unwrap_or(self.land_tax_per_tile);
+        self.tax_period = tax_period.unwrap_or(self.tax_period);
+        Ok(())
+    }
+
+    pub fn describe(&self) -> String {
+        let tax = match self.tax_period {
+            0 => "no land tax".to_string(),
+            period => format!("land tax ${} per tile every {} ticks", self.land_tax_per_tile, period),
+        };
+        format!("Transaction fee {:.1}% | {}", self.transaction_rate * 100.0, tax)
+    }
+}
+
+/// Every transaction since the ledger was started, oldest first.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Ledger {
+    pub transactions: Vec<Transaction>,
+}
+
+impl Ledger {
+    /// Moves `net` into `wallet` (out of it, if negative) and records the transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record(
+        &mut self,
+        wallet: &mut Money,
+        tick: u64,
+        kind: TransactionKind,
+        item: &str,
+        quantity: u32,
+        gross: Money,
+        fees: Money,
+        net: Money,
+    ) {
+        *wallet += net;
+        // Repeated expenses, such as watering a whole row, make one entry
+        if let Some(last) = self.transactions.last_mut().filter(|last| {
+            kind == TransactionKind::Expense
+                && last.kind == kind
+                && last.tick == tick
+                && last.item == item
+                && last.unit_price.times(quantity) == gross
+        }) {
+            last.quantity += quantity;
+            last.fees += fees;
+            last.net += net;
+            return;
+        }
+        let unit_price = if quantity > 0 { Money::from_cents(gross.cents() / quantity as i64) } else { gross };
+        self.transactions.push(Transaction {
+            tick,
+            kind,
+            item: item.to_string(),
+            quantity,
+            unit_price,
+            fees,
+            net,
+        });
+    }
+
+    /// Pays `cost` out of `wallet` for a fee-free expense and records it.
+    pub fn pay(&mut self, wallet: &mut Money, tick: u64, kind: TransactionKind, item: &str, quantity: u32, cost: Money) {
+        self.record(wallet, tick, kind, item, quantity, cost, Money::ZERO, -cost);
+    }
+
+    /// Revenue, costs and profit per crop: produce sales against the seed
+    /// bought for it. Other spending is not tied to a crop.
+    pub fn profit_by_crop(&self) -> BTreeMap<String, Profit> {
+        let mut crops: BTreeMap<String, Profit> = BTreeMap::new();
+        for transaction in &self.transactions {
+            let crop = match transaction.kind {
+                TransactionKind::Sale => transaction.item.as_str(),
+                TransactionKind::Purchase => match economy::seed_species(&transaction.item) {
+                    Some(species) => species,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            crops.entry(crop.to_string()).or_default().add(transaction.net);
+        }
+        crops
+    }
+
+    /// Revenue, costs and profit for each `period` ticks, keyed by the first
+    /// tick of the period.
+    pub fn profit_by_period(&self, period: u64) -> BTreeMap<u64, Profit> {
+        let period = period.max(1);
+        let mut periods: BTreeMap<u64, Profit> = BTreeMap::new();
+        for transaction in &self.transactions {
+            periods.entry(transaction.tick / period * period).or_default().add(transaction.net);
+        }
+        periods
+    }
+}
+
+/// Money in and money out of some slice of the ledger.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Profit {
+    pub revenue: Money,
+    pub costs: Money,
+}
+
+impl Profit {
+    fn add(&mut self, net: Money) {
+        if net.is_negative() {
+            self.costs -= net;
+        } else {
+            self.revenue += net;
+        }
+    }
+
+    pub fn net(&self) -> Money {
+        self.revenue - self.costs
+    }
+}
+
+/// The `ledger` report: the most recent transactions, then profit and loss
+/// by crop and by `period`.
+pub fn ledger_report(ledger: &Ledger, period: u64, recent: usize) -> String {
+    let mut report = String::from("Tick\tKind\t\tQty\tUnit\tFees\tNet\tItem\n");
+    let skip = ledger.transactions.len().saturating_sub(recent);
+    for transaction in ledger.transactions.iter().skip(skip) {
+        report.push_str(&format!(
+            "{}\t{:<10}\t{}\t{}\t{}\t{}\t{}\n",
+            transaction.tick,
+            format!("{:?}", transaction.kind),
+            transaction.quantity,
+            transaction.unit_price,
+            transaction.fees,
+            transaction.net,
+            transaction.item
+        ));
+    }
+
+    report.push_str("\nProfit and loss by crop:\nRevenue\tCosts\tNet\tCrop\n");
+    for (crop, profit) in ledger.profit_by_crop() {
+        report.push_str(&format!("{}\t{}\t{}\t{}\n", profit.revenue, profit.costs, profit.net(), crop));
+    }
+
+    report.push_str(&format!("\nProfit and loss per {} ticks:\nTicks\t\tRevenue\tCosts\tNet\n", period.max(1)));
+    for (start, profit) in ledger.profit_by_period(period) {
+        report.push_str(&format!(
+            "{}-{}\t\t{}\t{}\t{}\n",
+            start,
+            start + period.max(1) - 1,
+            profit.revenue,
+            profit.costs,
+            profit.net()
+        ));
+    }
+    let total: Money = ledger.transactions.iter().map(|transaction| transaction.net).sum();
+    report.push_str(&format!("\nNet over the whole ledger: {}\n", total));
+    report
+}
@@ -0,0 +1,96 @@
+// src/light.rs
+
+use crate::garden::Plot;
+use crate::weather::{Season, Weather};
+
+/// Light blocked by a taller plant directly beside a tile, scaled by how much taller it is.
+const ORTHOGONAL_SHADE: f32 = 0.15;
+/// Light blocked by a taller plant diagonally beside a tile.
+const DIAGONAL_SHADE: f32 = 0.075;
+/// Even a fully surrounded tile gets some scattered light.
+const MAX_SHADE: f32 = 0.9;
+
+/// Hours of direct sun an unshaded tile gets in a day with this weather and season.
+pub fn sunlight_hours(weather: Weather, season: Season) -> f32 {
+    season.daylight_hours() * weather.sun_fraction()
+}
+
+/// The parts of a day, each a third of its daylight, with the sun in a
+/// different quarter of the sky. Rows run north to south and columns west to
+/// east, so the sun starts on the east side of a plot, crosses the south side
+/// at midday and sets on the west side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeOfDay {
+    Morning,
+    Midday,
+    Evening,
+}
+
+impl TimeOfDay {
+    pub const ALL: [TimeOfDay; 3] = [TimeOfDay::Morning, TimeOfDay::Midday, TimeOfDay::Evening];
+
+    /// The direction of the sun as a (column, row) step across the plot.
+    pub fn sun_direction(&self) -> (i32, i32) {
+        match self {
+            TimeOfDay::Morning => (1, 0),
+            TimeOfDay::Midday => (0, 1),
+            TimeOfDay::Evening => (-1, 0),
+        }
+    }
+}
+
+/// Hours of direct sun reaching each tile of `plot`, after shading from taller neighbouring plants.
+/// A neighbour on the sun's side casts twice its usual shade and one on the far side none, so
+/// over a day a plant loses the morning sun to tall plants east of it, the midday sun to those
+/// south of it and the evening sun to those west of it.
+pub fn light_levels(plot: &Plot, weather: Weather, season: Season) -> Vec<Vec<f32>> {
+    let heights: Vec<Vec<f32>> = plot
+        .grid
+        .tiles
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|tile| tile.plant.as_ref().map_or(0.0, |plant| plant.current_height()))
+                .collect()
+        })
+        .collect();
+    let sun_per_part = sunlight_hours(weather, season) / TimeOfDay::ALL.len() as f32;
+
+    (0..heights.len())
+        .map(|y| {
+            (0..heights[y].len())
+                .map(|x| {
+                    TimeOfDay::ALL
+                        .iter()
+                        .map(|time| sun_per_part * (1.0 - shade(&heights, x, y, *time)))
+                        .sum()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The share of light at (`x`, `y`) at `time` blocked by taller plants in the eight surrounding tiles.
+fn shade(heights: &[Vec<f32>], x: usize, y: usize, time: TimeOfDay) -> f32 {
+    let own_height = heights[y][x];
+    let (sun_x, sun_y) = time.sun_direction();
+    let mut blocked = 0.0;
+    for dy in -1i32..=1 {
+        for dx in -1i32..=1 {
+            if dx == 0 && dy == 0 {
+                continue;
+            }
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            if nx < 0 || ny < 0 || ny as usize >= heights.len() || nx as usize >= heights[ny as usize].len() {
+                continue;
+            }
+            let neighbour_height = heights[ny as usize][nx as usize];
+            if neighbour_height > own_height {
+                let weight = if dx == 0 || dy == 0 { ORTHOGONAL_SHADE } else { DIAGONAL_SHADE };
+                let facing_sun = (1 + dx * sun_x + dy * sun_y).max(0) as f32;
+                blocked += weight * facing_sun * (neighbour_height - own_height) / neighbour_height;
+            }
+        }
+    }
+    blocked.min(MAX_SHADE)
+}
@@ -0,0 +1,106 @@
+// src/lineage.rs
+
+use crate::plant::{Plant, PlantGenetics};
+use crate::seeds::SeedLot;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AncestorKind {
+    Plant,
+    SeedLot,
+}
+
+/// What the pedigree remembers of a plant or seed lot, kept after the plant
+/// is harvested or the lot is sown out.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Ancestor {
+    pub id: u32,
+    pub kind: AncestorKind,
+    pub species: String,
+    /// A plant's parent is the seed lot it grew from; a seed lot's are the
+    /// plants it was collected or bred from. Empty for bought seed.
+    pub parents: Vec<u32>,
+    pub generation: u32,
+    /// The traits it expressed, or for a seed lot those of its seed parent.
+    pub genetics: PlantGenetics,
+    /// The tick it was planted or collected on.
+    pub recorded_at: u64,
+}
+
+impl Ancestor {
+    pub fn of_plant(plant: &Plant, recorded_at: u64) -> Ancestor {
+        Ancestor {
+            id: plant.id,
+            kind: AncestorKind::Plant,
+            species: plant.species.clone(),
+            parents: plant.parents.clone(),
+            generation: plant.generation,
+            genetics: plant.genetics.clone(),
+            recorded_at,
+        }
+    }
+
+    /// `genetics` are the traits of the lot's seed parent; see `SeedLot::mother`.
+    pub fn of_seed_lot(lot: &SeedLot, genetics: PlantGenetics) -> Ancestor {
+        Ancestor {
+            id: lot.id,
+            kind: AncestorKind::SeedLot,
+            species: lot.species.clone(),
+            parents: lot.parents.clone(),
+            generation: lot.generation,
+            genetics,
+            recorded_at: lot.collected_at,
+        }
+    }
+
+    /// One line such as "#7 plant Crimson Bloom, gen 1: yield 3-7, light 4.8h, ...".
+    fn describe(&self) -> String {
+        let kind = match self.kind {
+            AncestorKind::Plant => "plant",
+            AncestorKind::SeedLot => "seed lot",
+        };
+        let genetics = &self.genetics;
+        format!(
+            "#{} {} {}, gen {}: yield {}-{}, light {:.1}h, pest res {:.2}, disease res {:.2}, stability {:.2}",
+            self.id,
+            kind,
+            self.species,
+            self.generation,
+            genetics.yield_range.0,
+            genetics.yield_range.1,
+            genetics.light_req,
+            genetics.pest_resistance,
+            genetics.disease_resistance,
+            genetics.genetic_stability
+        )
+    }
+}
+
+/// The family tree of `id`, one ancestor per line with its parents indented
+/// beneath it, or `None` if the pedigree has no such plant or seed lot.
+pub fn family_tree(pedigree: &BTreeMap<u32, Ancestor>, id: u32) -> Option<String> {
+    let root = pedigree.get(&id)?;
+    let mut tree = root.describe();
+    push_parents(pedigree, root, "", &mut tree);
+    Some(tree)
+}
+
+fn push_parents(pedigree: &BTreeMap<u32, Ancestor>, child: &Ancestor, indent: &str, tree: &mut String) {
+    for (i, parent_id) in child.parents.iter().enumerate() {
+        let last = i + 1 == child.parents.len();
+        let (branch, continuation) = if last { ("└── ", "    ") } else { ("├── ", "│   ") };
+        tree.push('\n');
+        tree.push_str(indent);
+        tree.push_str(branch);
+        // Ids are handed out in order, so a parent always has a smaller id
+        // than its child and the walk cannot loop.
+        match pedigree.get(parent_id).filter(|parent| parent.id < child.id) {
+            Some(parent) => {
+                tree.push_str(&parent.describe());
+                push_parents(pedigree, parent, &format!("{}{}", indent, continuation), tree);
+            }
+            None => tree.push_str(&format!("#{} (no record)", parent_id)),
+        }
+    }
+}
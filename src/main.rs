@@ -1,16 +1,30 @@
 // src/main.rs
 
+mod beneficials;
+mod breeding;
+mod catalogue;
 mod cli;
+mod disease;
 mod economy;
 mod engine;
 mod events;
 mod garden;
+mod genome;
+mod ledger;
+mod light;
+mod lineage;
+mod money;
+mod orders;
 mod pests;
 mod plant;
 mod plant_definitions;
+mod rng;
 mod saveload;
+mod seeds;
+mod targeting;
 mod tui;
 mod tests;
+mod treatment;
 mod weather;
 
 fn main() {
@@ -18,48 +32,69 @@ fn main() {
 
     let args = cli::parse_args();
     println!("Command-line arguments: {:?}", args);
-    // Handle commands that are one-off actions and might exit early.
-    // We match on a reference to `args.command` so we can use it again later.
-    match &args.command {
-        // The Save command from `main` branch loads the default game, saves it to a new file, and exits.
-        cli::Commands::Save { filename } => {
-            // First, get the current game state to save. We load the default save file, or create a new game if none exists.
-            let game_state = saveload::load_game("default_save.json").unwrap_or_else(|_| engine::new_game());
-            // Then, perform the save operation.
-            saveload::save_game(&game_state, filename).unwrap();
-            println!("Game saved to {}", filename);
-            return; // Exit after saving, as was the behavior in the `main` branch.
-        }
-        // The Load command is now handled in the main state initialization below.
-        _ => { /* Continue to main logic */ }
+
+    // A species catalogue has to be in place before any game state refers to it
+    let mut species_catalogue = None;
+    if let Some(path) = &args.catalogue {
+        let loaded = catalogue::read_definitions(path)
+            .and_then(|definitions| Ok((catalogue::Catalogue::from_definitions(&definitions)?, definitions)));
+        match loaded {
+            Ok((loaded, definitions)) => {
+                catalogue::install(loaded);
+                species_catalogue = Some(definitions);
+            }
+            Err(e) => {
+                println!("Error loading species catalogue {}: {}", path, e);
+                return;
+            }
+        }
     }
 
-    // Determine the initial game state based on the command, or by loading the default.
+    // Determine the initial game state based on the command, or by loading the active save.
     let mut game_state = match &args.command {
-        cli::Commands::New => {
-            println!("Starting a new game.");
-            engine::new_game()
+        cli::Commands::New { seed } => {
+            let seed = seed.unwrap_or_else(rng::random_seed);
+            println!("Starting a new game with seed {}.", seed);
+            engine::new_game_with_seed(seed)
         }
         cli::Commands::Load { filename } => {
             println!("Loading game from {}.", filename);
-            saveload::load_game(filename).expect("Failed to load game from specified file.")
-        }
-        // For `Plant` or any other command, load the default game state.
-        // If it doesn't exist, start a new game. This was the core logic from the `plant` branch.
-        _ => saveload::load_game("default_save.json").unwrap_or_else(|_| {
-            println!("No saved game found, starting a new one.");
-            engine::new_game()
-        }),
+            match saveload::load_game(filename) {
+                Ok(game_state) => game_state,
+                Err(e) => {
+                    println!("Error loading game from {}: {}", filename, e);
+                    return;
+                }
+            }
+        }
+        // For `Plant` or any other command, load the active save.
+        // If it doesn't exist, start a new game.
+        _ => match saveload::load_game(&args.save) {
+            Ok(game_state) => game_state,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                println!("No saved game found, starting a new one.");
+                engine::new_game()
+            }
+            Err(e) => {
+                println!("Error loading game from {}: {}", args.save, e);
+                return;
+            }
+        },
     };
+    if species_catalogue.is_some() {
+        game_state.catalogue = species_catalogue;
+    }
 
-    // Now, perform actions on the loaded or newly created game state.
+    // Now, perform actions on the loaded or newly created game state,
+    // and write the result back to the active save if anything changed.
+    let mutates_state = args.command.mutates_state();
     handle_command(args.command, &mut game_state);
-
-    let tomato = plant::create_plant("tomato");
-    println!("Created a plant: {}", tomato.species);
-
-    let price = economy::get_market_price("corn");
-    println!("The price of corn is: {}", price);
+    if mutates_state {
+        match saveload::save_game(&game_state, &args.save) {
+            Ok(()) => println!("Game saved to {}", args.save),
+            Err(e) => println!("Error saving game to {}: {}", args.save, e),
+        }
+    }
 
     // The TUI will take over the terminal, so we'll just call it and let it run.
     if let Err(e) = tui::draw_ui(&mut game_state) {
@@ -69,11 +104,17 @@ fn main() {
 
 fn handle_command(command: cli::Commands, game_state: &mut garden::MainGameState) {
     match command {
-        cli::Commands::New | cli::Commands::Load { .. } | cli::Commands::Save { .. } => {
+        cli::Commands::New { .. } | cli::Commands::Load { .. } => {
             // These are handled in the main function
         }
-        cli::Commands::View { .. } => {
-            if let Some(plot) = game_state.plots.get(&(0, 0)) {
+        cli::Commands::Save { filename } => match saveload::save_game(game_state, &filename) {
+            Ok(()) => println!("Game saved to {}", filename),
+            Err(e) => println!("Error saving game to {}: {}", filename, e),
+        },
+        cli::Commands::View { plot, .. } => {
+            let Some(plot) = select_plot(game_state, plot.as_deref()) else { return };
+            if let Some(plot) = game_state.plots.get(&plot) {
+                println!("Plot {}", plot.label());
                 for row in &plot.grid.tiles {
                     for tile in row {
                         let symbol = match &tile.plant {
@@ -84,94 +125,246 @@ fn handle_command(command: cli::Commands, game_state: &mut garden::MainGameState
                                 plant::LifeCycleStage::Mature => 'P',
                                 plant::LifeCycleStage::Fruiting => 'P',
                                 plant::LifeCycleStage::Withering => 'x',
+                                plant::LifeCycleStage::Dead => 'd',
                             },
-                            None => '.',
+                            None => tui::weed_symbol(tile.soil.weeds),
                         };
-                        print!("{} ", symbol);
+                        let marker = if tile.plant.is_some() { tui::weed_marker(tile.soil.weeds) } else { ' ' };
+                        print!("{}{}", symbol, marker);
                     }
                     println!();
                 }
             }
         }
-        cli::Commands::Plant { x, y, seed } => {
-            engine::plant_seed(game_state, x, y, &seed);
-        }
-        cli::Commands::Water { x, y } => {
-            if let Some(plot) = game_state.plots.get_mut(&(0, 0)) {
-                if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
-                    tile.soil.soil_moisture += 0.2;
-                    tile.soil.soil_moisture = tile.soil.soil_moisture.clamp(0.0, 1.0);
-                    println!("Watered tile ({}, {}). New moisture: {}", x, y, tile.soil.soil_moisture);
-                } else {
-                    println!("Invalid coordinates: ({}, {})", x, y);
+        cli::Commands::Plant { target, seed, seed_lot } => {
+            if let Some(species) = seed.as_deref() {
+                if plant::species_template(&game_state.hybrids, species).is_none() {
+                    println!("Error planting: {}", plant::unknown_species(species));
+                    return;
+                }
+                if game_state.seed_inventory.get(species).copied().unwrap_or(0) == 0 {
+                    println!("Error planting: {}", seeds::no_seeds(species));
+                    return;
                 }
             }
+            let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+            let summary = engine::apply_to_selection(game_state, plot, &selection, |state, plot, x, y| {
+                match (&seed, seed_lot) {
+                    (_, Some(lot_id)) => engine::plant_seed_lot(state, plot, x, y, lot_id),
+                    (Some(seed), None) => engine::plant_seed(state, plot, x, y, seed),
+                    (None, None) => false,
+                }
+            });
+            println!("{}", summary.report("Planted"));
         }
-        cli::Commands::Fertilize { x, y, npk_mix } => {
-            if let Some(plot) = game_state.plots.get_mut(&(0, 0)) {
-                if let Some(tile) = plot.grid.tiles.get_mut(y as usize).and_then(|row| row.get_mut(x as usize)) {
-                    // For simplicity, we'll parse a string like "0.1,0.1,0.1" for NPK values
-                    let parts: Vec<Result<f32, _>> = npk_mix.split(',').map(|s| s.trim().parse()).collect();
-                    if parts.len() == 3 && parts.iter().all(|p| p.is_ok()) {
-                        let n = parts[0].as_ref().unwrap();
-                        let p = parts[1].as_ref().unwrap();
-                        let k = parts[2].as_ref().unwrap();
-
-                        tile.soil.soil_nutrients.nitrogen += n;
-                        tile.soil.soil_nutrients.phosphorus += p;
-                        tile.soil.soil_nutrients.potassium += k;
-
-                        // Clamp nutrient values
-                        tile.soil.soil_nutrients.nitrogen = tile.soil.soil_nutrients.nitrogen.clamp(0.0, 1.0);
-                        tile.soil.soil_nutrients.phosphorus = tile.soil.soil_nutrients.phosphorus.clamp(0.0, 1.0);
-                        tile.soil.soil_nutrients.potassium = tile.soil.soil_nutrients.potassium.clamp(0.0, 1.0);
-
-                        println!("Fertilized tile ({}, {}).", x, y);
-                    } else {
-                        println!("Invalid NPK mix format. Please use a format like '0.1,0.1,0.1'.");
-                    }
-                } else {
-                    println!("Invalid coordinates: ({}, {})", x, y);
+        cli::Commands::Water { target } => {
+            let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+            let summary = engine::apply_to_selection(game_state, plot, &selection, engine::water_tile);
+            println!("{}", summary.report("Watered"));
+        }
+        cli::Commands::Fertilize { target, npk_mix } => {
+            // For simplicity, we'll parse a string like "0.1,0.1,0.1" for NPK values
+            let parts: Vec<Result<f32, _>> = npk_mix.split(',').map(|s| s.trim().parse()).collect();
+            let valid = |p: &Result<f32, _>| p.as_ref().is_ok_and(|amount: &f32| amount.is_finite() && *amount >= 0.0);
+            if parts.len() != 3 || !parts.iter().all(valid) {
+                println!("Invalid NPK mix. Please give three amounts of zero or more, like '0.1,0.1,0.1'.");
+                return;
+            }
+            let npk = (
+                *parts[0].as_ref().unwrap(),
+                *parts[1].as_ref().unwrap(),
+                *parts[2].as_ref().unwrap(),
+            );
+            let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+            let summary = engine::apply_to_selection(game_state, plot, &selection, |state, plot, x, y| {
+                engine::fertilize_tile(state, plot, x, y, npk)
+            });
+            println!("{}", summary.report("Fertilized"));
+        }
+        cli::Commands::Harvest { target, collect_seeds } => {
+            let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+            let harvest = if collect_seeds { engine::collect_seeds } else { engine::harvest };
+            let summary = engine::apply_to_selection(game_state, plot, &selection, harvest);
+            println!("{}", summary.report("Harvested"));
+        }
+        cli::Commands::Breed { mother, father, plot } => {
+            let Some(plot) = select_plot(game_state, plot.as_deref()) else { return };
+            match engine::breed(game_state, plot, mother, father) {
+                Ok(lot_id) => {
+                    let lot = &game_state.seed_lots[&lot_id];
+                    println!("Bred {} {} seeds as seed lot {}.", lot.quantity, lot.species, lot_id);
                 }
+                Err(e) => println!("Error breeding: {}", e),
             }
         }
-        cli::Commands::Harvest { x, y } => {
-            engine::harvest(game_state, x, y);
+        cli::Commands::Lineage { id } => match lineage::family_tree(&game_state.pedigree, id) {
+            Some(tree) => println!("{}", tree),
+            None => println!("No plant or seed lot with id {}", id),
+        },
+        cli::Commands::Seeds => {
+            println!("Bought seed:");
+            print!("{}", seeds::list_seed_inventory(&game_state.seed_inventory));
+            println!("\nSeed lots:");
+            print!("{}", seeds::list_seed_lots(&game_state.seed_lots, &game_state.hybrids));
+        }
+        cli::Commands::Treat { treatment, target } => {
+            let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+            let summary = engine::treat_selection(game_state, plot, &selection, treatment);
+            println!("{}", summary.report("Treated"));
+        }
+        cli::Commands::Pesticide { target } => {
+            println!("`pesticide` is deprecated; applying neem oil, the broad-spectrum treatment. Use `treat` instead.");
+            let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+            let summary = engine::treat_selection(game_state, plot, &selection, treatment::Treatment::NeemOil);
+            println!("{}", summary.report("Treated"));
         }
-        cli::Commands::Pesticide { x, y } => {
-            engine::apply_pesticide(game_state, x, y);
+        cli::Commands::Inspect { target } => {
+            let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+            engine::apply_to_selection(game_state, plot, &selection, |state, plot, x, y| {
+                match engine::inspect_tile(state, plot, x, y) {
+                    Some(report) => println!("{}", report),
+                    None => println!("Invalid coordinates: ({}, {})", x, y),
+                }
+                true
+            });
+        }
+        cli::Commands::Weed { target, mulch } => {
+            let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+            let summary = engine::apply_to_selection(game_state, plot, &selection, |state, plot, x, y| {
+                engine::weed_tile(state, plot, x, y, mulch)
+            });
+            println!("{}", summary.report("Weeded"));
+        }
+        cli::Commands::Clear { target } => {
+            let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+            let summary = engine::apply_to_selection(game_state, plot, &selection, engine::clear_tile);
+            println!("{}", summary.report("Cleared"));
+        }
+        cli::Commands::Soil(soil_command) => match soil_command.command {
+            cli::SoilCommands::Amend { target, with } => {
+                let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+                let summary = engine::apply_to_selection(game_state, plot, &selection, |state, plot, x, y| {
+                    engine::amend_soil(state, plot, x, y, with)
+                });
+                println!("{}", summary.report("Amended"));
+            }
+            cli::SoilCommands::Lime { target } => {
+                let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+                let summary = engine::apply_to_selection(game_state, plot, &selection, |state, plot, x, y| {
+                    engine::adjust_ph(state, plot, x, y, true)
+                });
+                println!("{}", summary.report("Limed"));
+            }
+            cli::SoilCommands::Sulfur { target } => {
+                let Some((plot, selection)) = select_tiles(game_state, &target) else { return };
+                let summary = engine::apply_to_selection(game_state, plot, &selection, |state, plot, x, y| {
+                    engine::adjust_ph(state, plot, x, y, false)
+                });
+                println!("{}", summary.report("Sulfured"));
+            }
+        },
+        cli::Commands::Land(land_command) => match land_command.command {
+            cli::LandCommands::Buy { at, size, name } => match engine::buy_land(game_state, at, size, name) {
+                Ok(cost) => println!("Bought a {}x{} plot at ({}, {}) for ${}.", size.0, size.1, at.0, at.1, cost),
+                Err(e) => println!("Error buying land: {}", e),
+            },
+            cli::LandCommands::List => {
+                print!("{}", engine::list_land(game_state));
+            }
+        },
+        cli::Commands::Ledger { period, recent } => {
+            print!("{}", ledger::ledger_report(&game_state.ledger, period, recent));
+        }
+        cli::Commands::Fees { transaction_rate, land_tax, tax_period } => {
+            match game_state.fees.update(transaction_rate, land_tax, tax_period) {
+                Ok(()) => println!("{}", game_state.fees.describe()),
+                Err(e) => println!("Error changing fees: {}", e),
+            }
         }
         cli::Commands::Forecast { ticks } => {
             engine::forecast(game_state, ticks);
         }
+        cli::Commands::Advance { ticks, weather } => {
+            let summary = engine::advance(game_state, ticks, weather);
+            print!("{}", summary.report());
+        }
         cli::Commands::Market(market_command) => match market_command.command {
             cli::MarketCommands::Buy { item, quantity } => {
-                match economy::buy_item(
-                    &mut game_state.inventory,
-                    &mut game_state.wallet,
-                    &game_state.market,
-                    &item,
-                    quantity,
-                ) {
-                    Ok(()) => println!("Bought {} {}(s).", quantity, item),
+                match engine::buy(game_state, &item, quantity) {
+                    Ok(cost) => println!("Bought {} {}(s) for {} including fees.", quantity, item, cost),
                     Err(e) => println!("Error buying item: {}", e),
                 }
             }
             cli::MarketCommands::Sell { item, quantity } => {
-                match economy::sell_item(
-                    &mut game_state.inventory,
-                    &mut game_state.wallet,
-                    &mut game_state.market,
-                    &item,
-                    quantity,
-                ) {
-                    Ok(()) => println!("Sold {} {}(s).", quantity, item),
+                match engine::sell(game_state, &item, quantity) {
+                    Ok(proceeds) => println!("Sold {} {}(s) for {} after fees.", quantity, item, proceeds),
                     Err(e) => println!("Error selling item: {}", e),
                 }
             }
-            cli::MarketCommands::View => {
-                println!("{}", economy::view_market(&game_state.market));
+            cli::MarketCommands::View { kind } => {
+                println!("{}", economy::view_market(&game_state.market, kind));
+            }
+            cli::MarketCommands::Order { side, item, quantity, at_or_above, at_or_below, expires } => {
+                let trigger = match (at_or_above, at_or_below) {
+                    (Some(limit), _) => orders::Trigger::AtOrAbove(limit),
+                    (None, Some(limit)) => orders::Trigger::AtOrBelow(limit),
+                    (None, None) => return,
+                };
+                match engine::place_order(game_state, side, &item, quantity, trigger, expires) {
+                    Ok(id) => println!("Placed order #{}.", id),
+                    Err(e) => println!("Error placing order: {}", e),
+                }
+            }
+            cli::MarketCommands::Orders => {
+                print!("{}", orders::list_orders(&game_state.orders));
+            }
+            cli::MarketCommands::Cancel { id } => match engine::cancel_order(game_state, id) {
+                Ok(order) => println!("Cancelled order #{} for {} {}.", order.id, order.remaining, order.item),
+                Err(e) => println!("Error cancelling order: {}", e),
+            },
+            cli::MarketCommands::History { item, ticks } => {
+                match economy::price_history_report(&game_state.market, &item, ticks) {
+                    Ok(report) => println!("{}", report),
+                    Err(e) => println!("Error showing price history: {}", e),
+                }
+            }
+        },
+        cli::Commands::Species(species_command) => match species_command.command {
+            cli::SpeciesCommands::List { category } => {
+                print!("{}", catalogue::list_species(&game_state.hybrids, &game_state.market, category));
+            }
+            cli::SpeciesCommands::Show { name } => {
+                match catalogue::show_species(&game_state.hybrids, &game_state.market, &name) {
+                    Some(report) => println!("{}", report),
+                    None => println!("Error: {}", plant::unknown_species(&name)),
+                }
             }
         },
     }
 }
+
+/// Resolves a `--plot` selector, reporting an error if it matches no owned plot.
+fn select_plot(game_state: &garden::MainGameState, selector: Option<&str>) -> Option<(i32, i32)> {
+    match garden::resolve_plot(game_state, selector) {
+        Ok(plot) => Some(plot),
+        Err(e) => {
+            println!("{}", e);
+            None
+        }
+    }
+}
+
+/// Resolves the plot and tile selection of a tile command, reporting any error.
+fn select_tiles(
+    game_state: &garden::MainGameState,
+    target: &cli::TargetArgs,
+) -> Option<((i32, i32), targeting::TileSelection)> {
+    let plot = select_plot(game_state, target.plot.as_deref())?;
+    match target.selection() {
+        Ok(selection) => Some((plot, selection)),
+        Err(e) => {
+            println!("{}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,131 @@
+// src/money.rs
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
+/// An amount of money held exactly as a whole number of cents, so sums of
+/// many small payments never drift the way `f32` arithmetic does. Prices
+/// are still modelled as `f32` and rounded to the cent when money changes hands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub const fn from_cents(cents: i64) -> Money {
+        Money(cents)
+    }
+
+    /// `amount` rounded to the nearest cent.
+    pub fn from_f32(amount: f32) -> Money {
+        Money((amount as f64 * 100.0).round() as i64)
+    }
+
+    pub fn cents(&self) -> i64 {
+        self.0
+    }
+
+    /// This amount `quantity` times over, saturating rather than overflowing.
+    pub fn times(&self, quantity: u32) -> Money {
+        Money(self.0.saturating_mul(quantity as i64))
+    }
+
+    /// This amount times `factor`, rounded to the cent.
+    pub fn scale(&self, factor: f32) -> Money {
+        Money((self.0 as f64 * factor as f64).round() as i64)
+    }
+
+    pub fn abs(&self) -> Money {
+        Money(self.0.abs())
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        write!(f, "{}{}.{:02}", sign, self.0.abs() / 100, self.0.abs() % 100)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+/// Saved as a whole number of cents.
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.0)
+    }
+}
+
+/// Reads a whole number as cents, and a number with a fraction (as saves
+/// from before fixed-point money wrote the wallet) as dollars.
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        struct MoneyVisitor;
+
+        impl Visitor<'_> for MoneyVisitor {
+            type Value = Money;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a number of cents or a decimal amount of dollars")
+            }
+
+            fn visit_i64<E: de::Error>(self, cents: i64) -> Result<Money, E> {
+                Ok(Money(cents))
+            }
+
+            fn visit_u64<E: de::Error>(self, cents: u64) -> Result<Money, E> {
+                i64::try_from(cents).map(Money).map_err(E::custom)
+            }
+
+            fn visit_f64<E: de::Error>(self, dollars: f64) -> Result<Money, E> {
+                Ok(Money((dollars * 100.0).round() as i64))
+            }
+        }
+
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
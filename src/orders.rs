@@ -0,0 +1,119 @@
+// src/orders.rs
+
+use crate::money::Money;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// The price condition an order waits for.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    AtOrAbove(f32),
+    AtOrBelow(f32),
+}
+
+impl Trigger {
+    /// The price the order waits for.
+    pub fn price(&self) -> f32 {
+        match *self {
+            Trigger::AtOrAbove(limit) | Trigger::AtOrBelow(limit) => limit,
+        }
+    }
+
+    pub fn is_met(&self, price: f32) -> bool {
+        match *self {
+            Trigger::AtOrAbove(limit) => price >= limit,
+            Trigger::AtOrBelow(limit) => price <= limit,
+        }
+    }
+}
+
+/// A standing order to trade once the price meets its trigger.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Order {
+    pub id: u32,
+    pub side: Side,
+    pub item: String,
+    /// Units still to trade.
+    pub remaining: u32,
+    pub trigger: Trigger,
+    pub placed_at: u64,
+    /// The last tick the order may fill on, if it expires at all.
+    pub expires_at: Option<u64>,
+}
+
+impl Order {
+    /// A limit order (sell high, buy low) trades each unit only while the
+    /// price still meets the trigger, so slippage can leave it part-filled.
+    /// A stop order (sell on a fall, buy on a rise) trades everything it can
+    /// once triggered, whatever the price then does.
+    pub fn is_limit(&self) -> bool {
+        matches!(
+            (self.side, self.trigger),
+            (Side::Sell, Trigger::AtOrAbove(_)) | (Side::Buy, Trigger::AtOrBelow(_))
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    PartiallyFilled,
+    Filled,
+    Expired,
+}
+
+/// What happened to one order during a tick.
+#[derive(Debug, Clone)]
+pub struct OrderUpdate {
+    pub order_id: u32,
+    pub side: Side,
+    pub item: String,
+    /// Units traded this tick.
+    pub quantity: u32,
+    /// Proceeds of a sale or cost of a purchase, fees included.
+    pub value: Money,
+    pub status: OrderStatus,
+}
+
+impl OrderUpdate {
+    pub fn describe(&self) -> String {
+        let status = match self.status {
+            OrderStatus::PartiallyFilled => "partly filled",
+            OrderStatus::Filled => "filled",
+            OrderStatus::Expired => "expired",
+        };
+        if self.quantity == 0 {
+            return format!("order #{} for {} {}", self.order_id, self.item, status);
+        }
+        let verb = match self.side {
+            Side::Buy => "bought",
+            Side::Sell => "sold",
+        };
+        format!(
+            "order #{} {} {} {} for {} ({})",
+            self.order_id, verb, self.quantity, self.item, self.value, status
+        )
+    }
+}
+
+/// A table of the open orders.
+pub fn list_orders(orders: &BTreeMap<u32, Order>) -> String {
+    let mut listing = String::from("Id\tSide\tQty\tItem\t\t\tTrigger\t\tExpires\n");
+    for order in orders.values() {
+        let trigger = match order.trigger {
+            Trigger::AtOrAbove(limit) => format!(">= {:.2}", limit),
+            Trigger::AtOrBelow(limit) => format!("<= {:.2}", limit),
+        };
+        let expires = order.expires_at.map_or("never".to_string(), |tick| format!("tick {}", tick));
+        listing.push_str(&format!(
+            "{}\t{:?}\t{}\t{}\t\t{}\t\t{}\n",
+            order.id, order.side, order.remaining, order.item, trigger, expires
+        ));
+    }
+    listing
+}
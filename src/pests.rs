@@ -1,3 +1,5 @@
+use crate::garden::Soil;
+use crate::weather::Weather;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -12,3 +14,85 @@ pub enum PestType {
     SpiderMites,
     Whiteflies,
 }
+
+/// How a pest type behaves once it has found a plant.
+pub struct PestProfile {
+    /// Infestation gained per tick.
+    pub growth_rate: f32,
+    /// Chance per tick, at full infestation, of reaching a neighbouring plant.
+    pub spread_chance: f32,
+    /// Plant health lost per tick at full infestation.
+    pub health_damage: f32,
+    /// Share of the plant's growth lost at full infestation.
+    pub growth_damage: f32,
+}
+
+impl PestType {
+    pub const ALL: [PestType; 3] = [PestType::Aphids, PestType::SpiderMites, PestType::Whiteflies];
+
+    /// Aphids multiply fast and stunt growth by sucking sap; spider mites
+    /// are slow but chew through plant health; whiteflies do little damage
+    /// but fly between plants readily.
+    pub fn profile(&self) -> PestProfile {
+        match self {
+            PestType::Aphids => PestProfile {
+                growth_rate: 0.08,
+                spread_chance: 0.2,
+                health_damage: 0.06,
+                growth_damage: 0.4,
+            },
+            PestType::SpiderMites => PestProfile {
+                growth_rate: 0.05,
+                spread_chance: 0.15,
+                health_damage: 0.12,
+                growth_damage: 0.1,
+            },
+            PestType::Whiteflies => PestProfile {
+                growth_rate: 0.06,
+                spread_chance: 0.4,
+                health_damage: 0.05,
+                growth_damage: 0.2,
+            },
+        }
+    }
+
+    /// Chance per tick that this pest turns up on an uninfested plant, before
+    /// the plant's own resistance. Aphids seek out nitrogen-rich, lush
+    /// growth; spider mites boom in hot, dry spells; whiteflies like warmth
+    /// but are grounded by rain.
+    pub fn spawn_chance(&self, weather: Weather, soil: &Soil) -> f32 {
+        match self {
+            PestType::Aphids => {
+                let heat = if weather == Weather::Heatwave { 0.5 } else { 1.0 };
+                0.02 * (1.0 + 3.0 * soil.soil_nutrients.nitrogen) * heat
+            }
+            PestType::SpiderMites => {
+                let heat = match weather {
+                    Weather::Heatwave => 4.0,
+                    Weather::Sunny => 1.5,
+                    Weather::Cloudy => 0.5,
+                    Weather::Rainy => 0.2,
+                };
+                let dryness = if soil.soil_moisture < 0.3 { 2.0 } else { 1.0 };
+                0.015 * heat * dryness
+            }
+            PestType::Whiteflies => {
+                let warmth = match weather {
+                    Weather::Heatwave | Weather::Sunny => 1.5,
+                    Weather::Cloudy => 1.0,
+                    Weather::Rainy => 0.3,
+                };
+                0.02 * warmth
+            }
+        }
+    }
+
+    /// Multiplier on growth and damage under the current conditions.
+    pub fn activity(&self, weather: Weather, soil: &Soil) -> f32 {
+        match self {
+            PestType::SpiderMites if weather == Weather::Heatwave || soil.soil_moisture < 0.3 => 2.0,
+            PestType::Whiteflies if weather == Weather::Rainy => 0.5,
+            _ => 1.0,
+        }
+    }
+}
@@ -1,4 +1,6 @@
 // src/plant.rs
+use crate::garden::Nutrients;
+use crate::genome::Genome;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -11,9 +13,76 @@ pub struct Plant {
     pub wither_time: u32,
     pub growth_progress: f32,
     pub health: f32,
+    #[serde(default)]
+    pub deficiency: Deficiency,
+    /// How well bees have pollinated the plant's flowers, from 0.0 to 1.0.
+    #[serde(default)]
+    pub pollination: f32,
+    /// The gene pairs `genetics` is expressed from. Empty in saves made
+    /// before genomes existed until `load_game` fills it in.
+    #[serde(default)]
+    pub genome: Genome,
+    /// The plant's pedigree id; 0 for species templates.
+    #[serde(default)]
+    pub id: u32,
+    /// The seed lot it grew from, if it was not grown from bought seed.
+    #[serde(default)]
+    pub parents: Vec<u32>,
+    /// How many times its line has been grown from collected or bred seed.
+    #[serde(default)]
+    pub generation: u32,
 }
 
-#[derive(Serialize, Deserialize)]
+/// Effective soil level (after pH lock-up) below which a nutrient counts as deficient.
+pub const DEFICIENCY_THRESHOLD: f32 = 0.2;
+
+/// How short a plant is of each nutrient, from 0.0 (enough) to 1.0 (none
+/// available). Low nitrogen slows growth, low phosphorus cuts yield and low
+/// potassium weakens the plant against pests.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub struct Deficiency {
+    pub nitrogen: f32,
+    pub phosphorus: f32,
+    pub potassium: f32,
+}
+
+impl Deficiency {
+    /// Assesses the plant's shortfall given the soil's nutrients and how much
+    /// of each the plant can take up at the soil's pH.
+    pub fn assess(nutrients: &Nutrients, availability: (f32, f32, f32)) -> Deficiency {
+        let severity = |level: f32, available: f32| {
+            ((DEFICIENCY_THRESHOLD - level * available) / DEFICIENCY_THRESHOLD).clamp(0.0, 1.0)
+        };
+        Deficiency {
+            nitrogen: severity(nutrients.nitrogen, availability.0),
+            phosphorus: severity(nutrients.phosphorus, availability.1),
+            potassium: severity(nutrients.potassium, availability.2),
+        }
+    }
+
+    pub fn worst(&self) -> f32 {
+        self.nitrogen.max(self.phosphorus).max(self.potassium)
+    }
+
+    /// A short status such as "low N, severe P" or "none".
+    pub fn describe(&self) -> String {
+        let parts: Vec<String> = [("N", self.nitrogen), ("P", self.phosphorus), ("K", self.potassium)]
+            .iter()
+            .filter(|(_, severity)| *severity > 0.0)
+            .map(|(nutrient, severity)| {
+                let level = if *severity >= 0.5 { "severe" } else { "low" };
+                format!("{} {}", level, nutrient)
+            })
+            .collect();
+        if parts.is_empty() {
+            "none".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PlantGenetics {
     pub growth_time: u32,
     pub yield_range: (u32, u32),
@@ -23,9 +92,50 @@ pub struct PlantGenetics {
     pub pest_resistance: f32,
     pub disease_resistance: f32,
     pub genetic_stability: f32,
+    #[serde(default = "default_preferred_ph_range")]
+    pub preferred_ph_range: (f32, f32),
+    /// Full-grown height in metres; taller plants shade their neighbours.
+    #[serde(default = "default_height")]
+    pub height: f32,
+}
+
+fn default_preferred_ph_range() -> (f32, f32) {
+    (6.0, 7.0)
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Debug)]
+fn default_height() -> f32 {
+    0.5
+}
+
+/// How much each nutrient's availability drops per pH unit outside a plant's
+/// preferred range, as (N, P, K). Phosphorus locks up fastest, potassium slowest.
+const PH_SENSITIVITY: (f32, f32, f32) = (0.3, 0.5, 0.2);
+/// Availability never drops below this, however far off the pH is.
+const MIN_NUTRIENT_AVAILABILITY: f32 = 0.1;
+
+impl PlantGenetics {
+    /// The share of soil nitrogen, phosphorus and potassium this plant can
+    /// take up at `soil_ph`: everything inside its preferred range, less the
+    /// further the pH strays outside it.
+    pub fn nutrient_availability(&self, soil_ph: f32) -> (f32, f32, f32) {
+        let (low, high) = self.preferred_ph_range;
+        let distance = if soil_ph < low {
+            low - soil_ph
+        } else if soil_ph > high {
+            soil_ph - high
+        } else {
+            0.0
+        };
+        let availability = |sensitivity: f32| (1.0 - distance * sensitivity).max(MIN_NUTRIENT_AVAILABILITY);
+        (
+            availability(PH_SENSITIVITY.0),
+            availability(PH_SENSITIVITY.1),
+            availability(PH_SENSITIVITY.2),
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
 pub enum LifeCycleStage {
     Seed,
     Sprout,
@@ -33,60 +143,83 @@ pub enum LifeCycleStage {
     Mature,
     Fruiting,
     Withering,
+    /// Killed by poor health; stays on the tile until cleared.
+    Dead,
+}
+
+impl Plant {
+    /// How tall the plant stands now: it reaches its full height at `maturity_age`.
+    pub fn current_height(&self) -> f32 {
+        let grown = (self.age as f32 / self.maturity_age.max(1) as f32).clamp(0.1, 1.0);
+        self.genetics.height * grown
+    }
+
+    /// The life cycle stage a living plant of this age is in: it grows
+    /// through the first half of `maturity_age`, stands mature and
+    /// flowering through the last quarter, fruits from `maturity_age` and
+    /// withers from `wither_time`.
+    pub fn stage_for_age(&self) -> LifeCycleStage {
+        if self.age >= self.wither_time {
+            LifeCycleStage::Withering
+        } else if self.age >= self.maturity_age {
+            LifeCycleStage::Fruiting
+        } else if self.age >= self.maturity_age * 3 / 4 {
+            LifeCycleStage::Mature
+        } else if self.age >= self.maturity_age / 2 {
+            LifeCycleStage::Growing
+        } else if self.age > 0 {
+            LifeCycleStage::Sprout
+        } else {
+            LifeCycleStage::Seed
+        }
+    }
+}
+
+use crate::breeding::Hybrid;
+use crate::catalogue;
+use std::collections::BTreeMap;
+use rand::Rng;
+
+/// The template for `species`, whether catalogued or bred.
+pub fn species_template<'a>(hybrids: &'a BTreeMap<String, Hybrid>, species: &str) -> Option<&'a Plant> {
+    catalogue::catalogue()
+        .get(species)
+        .map(|entry| &entry.template)
+        .or_else(|| hybrids.get(species).map(|hybrid| &hybrid.template))
+}
+
+/// A new individual of `species`, with its own genome rolled around the species template.
+pub fn create_plant(species: &str, hybrids: &BTreeMap<String, Hybrid>, rng: &mut impl Rng) -> Result<Plant, String> {
+    let template = species_template(hybrids, species).ok_or_else(|| unknown_species(species))?;
+    let genome = Genome::individual(&template.genetics, rng);
+    Ok(grow_from_template(template, genome))
+}
+
+/// The error for a species that is neither catalogued nor bred.
+pub fn unknown_species(species: &str) -> String {
+    format!("Unknown species '{}'; see `species list`", species)
+}
+
+/// A plant of `species` grown from a seed carrying `genome`.
+pub fn create_plant_from_genome(species: &str, hybrids: &BTreeMap<String, Hybrid>, genome: Genome) -> Option<Plant> {
+    species_template(hybrids, species).map(|template| grow_from_template(template, genome))
 }
 
-use crate::plant_definitions::PLANTS;
-use rand::seq::SliceRandom;
-
-pub fn create_plant(species: &str) -> Plant {
-    PLANTS
-        .iter()
-        .find(|p| p.species == species)
-        .map(|p| {
-            let mut plant = Plant {
-                species: p.species.clone(),
-                genetics: PlantGenetics {
-                    growth_time: p.genetics.growth_time,
-                    yield_range: p.genetics.yield_range,
-                    ideal_moisture_range: p.genetics.ideal_moisture_range,
-                    nutrient_consumption: p.genetics.nutrient_consumption,
-                    light_req: p.genetics.light_req,
-                    pest_resistance: p.genetics.pest_resistance,
-                    disease_resistance: p.genetics.disease_resistance,
-                    genetic_stability: p.genetics.genetic_stability,
-                },
-                life_cycle_stage: LifeCycleStage::Seed,
-                age: 0,
-                maturity_age: p.maturity_age,
-                wither_time: p.wither_time,
-                growth_progress: 0.0,
-                health: 1.0,
-            };
-            plant
-        })
-        .unwrap_or_else(|| {
-            // Fallback to a random plant if species not found
-            let mut rng = rand::thread_rng();
-            let random_plant = PLANTS.choose(&mut rng).unwrap();
-            let mut plant = Plant {
-                species: random_plant.species.clone(),
-                genetics: PlantGenetics {
-                    growth_time: random_plant.genetics.growth_time,
-                    yield_range: random_plant.genetics.yield_range,
-                    ideal_moisture_range: random_plant.genetics.ideal_moisture_range,
-                    nutrient_consumption: random_plant.genetics.nutrient_consumption,
-                    light_req: random_plant.genetics.light_req,
-                    pest_resistance: random_plant.genetics.pest_resistance,
-                    disease_resistance: random_plant.genetics.disease_resistance,
-                    genetic_stability: random_plant.genetics.genetic_stability,
-                },
-                life_cycle_stage: LifeCycleStage::Seed,
-                age: 0,
-                maturity_age: random_plant.maturity_age,
-                wither_time: random_plant.wither_time,
-                growth_progress: 0.0,
-                health: 1.0,
-            };
-            plant
-        })
+fn grow_from_template(template: &Plant, genome: Genome) -> Plant {
+    Plant {
+        species: template.species.clone(),
+        genetics: genome.express(&template.genetics),
+        life_cycle_stage: LifeCycleStage::Seed,
+        age: 0,
+        maturity_age: template.maturity_age,
+        wither_time: template.wither_time,
+        growth_progress: 0.0,
+        health: 1.0,
+        deficiency: Deficiency::default(),
+        pollination: 0.0,
+        genome,
+        id: 0,
+        parents: Vec::new(),
+        generation: 0,
+    }
 }
@@ -1,234 +1,11 @@
 // src/plant_definitions.rs
 
-use crate::plant::{Plant, PlantGenetics, LifeCycleStage};
+use crate::catalogue::SpeciesDefinition;
 use once_cell::sync::Lazy;
-use rand::{Rng, SeedableRng};
-use rand::rngs::StdRng;
 
-pub struct PlantDefinition {
-    pub name: &'static str,
-    pub seed: u64,
-}
+/// The built-in species, written in the same format as a `--catalogue` file.
+const BUILT_IN: &str = include_str!("../data/species.json");
 
-pub static PLANTS: Lazy<Vec<Plant>> = Lazy::new(|| {
-    let definitions = vec![
-        PlantDefinition { name: "Crimson Bloom", seed: 12345 },
-        PlantDefinition { name: "Azure Fern", seed: 54321 },
-        PlantDefinition { name: "Golden Pine", seed: 98765 },
-        PlantDefinition { name: "Shadow Root", seed: 13579 },
-        PlantDefinition { name: "Silver Birch", seed: 24680 },
-        PlantDefinition { name: "Whispering Willow", seed: 11223 },
-        PlantDefinition { name: "Sunpetal", seed: 33445 },
-        PlantDefinition { name: "Moonpetal", seed: 55667 },
-        PlantDefinition { name: "Starflower", seed: 77889 },
-        PlantDefinition { name: "Dragon's Breath", seed: 99001 },
-        PlantDefinition { name: "Ghost Orchid", seed: 10111 },
-        PlantDefinition { name: "Glimmering Moss", seed: 21212 },
-        PlantDefinition { name: "Ruby Thorn", seed: 32323 },
-        PlantDefinition { name: "Sapphire Vine", seed: 43434 },
-        PlantDefinition { name: "Emerald Ivy", seed: 54545 },
-        PlantDefinition { name: "Obsidian Rose", seed: 65656 },
-        PlantDefinition { name: "Opal Cactus", seed: 76767 },
-        PlantDefinition { name: "Jade Bamboo", seed: 87878 },
-        PlantDefinition { name: "Topaz Tulip", seed: 98989 },
-        PlantDefinition { name: "Amethyst Lily", seed: 10101 },
-        PlantDefinition { name: "Garnet Poppy", seed: 20202 },
-        PlantDefinition { name: "Diamond Daisy", seed: 30303 },
-        PlantDefinition { name: "Pearl Blossom", seed: 40404 },
-        PlantDefinition { name: "Coral Bell", seed: 50505 },
-        PlantDefinition { name: "Quartz Crystal", seed: 60606 },
-        PlantDefinition { name: "Turquoise Iris", seed: 70707 },
-        PlantDefinition { name: "Lapis Lazuli Lupin", seed: 80808 },
-        PlantDefinition { name: "Malachite Marigold", seed: 90909 },
-        PlantDefinition { name: "Sunstone Sunflower", seed: 11111 },
-        PlantDefinition { name: "Moonstone Morning Glory", seed: 22222 },
-        PlantDefinition { name: "Bloodstone Bellflower", seed: 33333 },
-        PlantDefinition { name: "Tiger's Eye Thistle", seed: 44444 },
-        PlantDefinition { name: "Hawk's Eye Heather", seed: 55555 },
-        PlantDefinition { name: "Cat's Eye Clover", seed: 66666 },
-        PlantDefinition { name: "Serpentine Snapdragon", seed: 77777 },
-        PlantDefinition { name: "Agate Aloe", seed: 88888 },
-        PlantDefinition { name: "Jasper Jasmine", seed: 99999 },
-        PlantDefinition { name: "Onyx Orchid", seed: 10000 },
-        PlantDefinition { name: "Carnelian Crocus", seed: 20000 },
-        PlantDefinition { name: "Sodalite Snowdrop", seed: 30000 },
-        PlantDefinition { name: "Rhodonite Rhododendron", seed: 40000 },
-        PlantDefinition { name: "Kyanite Kohlrabi", seed: 50000 },
-        PlantDefinition { name: "Fluorite Foxglove", seed: 60000 },
-        PlantDefinition { name: "Aventurine Anemone", seed: 70000 },
-        PlantDefinition { name: "Amazonite Aster", seed: 80000 },
-        PlantDefinition { name: "Labradorite Lavender", seed: 90000 },
-        PlantDefinition { name: "Peridot Petunia", seed: 12121 },
-        PlantDefinition { name: "Spinel Zinnia", seed: 23232 },
-        PlantDefinition { name: "Zircon Geranium", seed: 34343 },
-        PlantDefinition { name: "Tanzanite Dahlia", seed: 45454 },
-        PlantDefinition { name: "Alexandrite Azalea", seed: 56565 },
-        PlantDefinition { name: "Morganite Magnolia", seed: 67676 },
-        PlantDefinition { name: "Heliodor Hibiscus", seed: 78787 },
-        PlantDefinition { name: "Aquamarine Buttercup", seed: 89898 },
-        PlantDefinition { name: "Goshenite Gladiolus", seed: 10112 },
-        PlantDefinition { name: "Bixbite Begonia", seed: 21223 },
-        PlantDefinition { name: "Fire Opal Freesia", seed: 32334 },
-        PlantDefinition { name: "Black Opal Oleander", seed: 43445 },
-        PlantDefinition { name: "Boulder Opal Bluebell", seed: 54556 },
-        PlantDefinition { name: "Matrix Opal Monkshood", seed: 65667 },
-        PlantDefinition { name: "Andamooka Opal Aconite", seed: 76778 },
-        PlantDefinition { name: "Lightning Ridge Opal Larkspur", seed: 87889 },
-        PlantDefinition { name: "Welsh Opal Wolfsbane", seed: 98990 },
-        PlantDefinition { name: "Honduran Opal Hollyhock", seed: 10102 },
-        PlantDefinition { name: "Peruvian Opal Peony", seed: 20203 },
-        PlantDefinition { name: "Ethiopian Opal Elderflower", seed: 30304 },
-        PlantDefinition { name: "Mexican Fire Opal Mimosa", seed: 40405 },
-        PlantDefinition { name: "Brazilian Opal Bougainvillea", seed: 50506 },
-        PlantDefinition { name: "Slovakian Opal Sweet Pea", seed: 60607 },
-        PlantDefinition { name: "Tanzanian Opal Tansy", seed: 70708 },
-        PlantDefinition { name: "Indonesian Opal Impatiens", seed: 80809 },
-        PlantDefinition { name: "Australian Opal Allium", seed: 90910 },
-        PlantDefinition { name: "Dragon's Eye", seed: 11112 },
-        PlantDefinition { name: "Phoenix Feather", seed: 22223 },
-        PlantDefinition { name: "Griffin's Claw", seed: 33334 },
-        PlantDefinition { name: "Unicorn's Horn", seed: 44445 },
-        PlantDefinition { name: "Mermaid's Scale", seed: 55556 },
-        PlantDefinition { name: "Fairy's Wing", seed: 66667 },
-        PlantDefinition { name: "Pixie Dust", seed: 77778 },
-        PlantDefinition { name: "Goblin's Gold", seed: 88889 },
-        PlantDefinition { name: "Troll's Treasure", seed: 99990 },
-        PlantDefinition { name: "Dwarf's Delight", seed: 10001 },
-        PlantDefinition { name: "Elf's Elegance", seed: 20002 },
-        PlantDefinition { name: "Giant's Growth", seed: 30003 },
-        PlantDefinition { name: "Nymph's Nectar", seed: 40004 },
-        PlantDefinition { name: "Satyr's Song", seed: 50005 },
-        PlantDefinition { name: "Centaur's Courage", seed: 60006 },
-        PlantDefinition { name: "Minotaur's Maze", seed: 70007 },
-        PlantDefinition { name: "Hydra's Head", seed: 80008 },
-        PlantDefinition { name: "Siren's Call", seed: 90009 },
-        PlantDefinition { name: "Harpy's Feather", seed: 12321 },
-        PlantDefinition { name: "Chimera's Charm", seed: 23432 },
-        PlantDefinition { name: "Kraken's Ink", seed: 34543 },
-        PlantDefinition { name: "Leviathan's Lullaby", seed: 45654 },
-        PlantDefinition { name: "Behemoth's Blessing", seed: 56765 },
-        PlantDefinition { name: "Ziz's Zephyr", seed: 67876 },
-        PlantDefinition { name: "Roc's Roar", seed: 78987 },
-        PlantDefinition { name: "Thunderbird's Cry", seed: 89098 },
-        PlantDefinition { name: "Quetzalcoatl's Crest", seed: 90109 },
-        PlantDefinition { name: "Fenrir's Fang", seed: 10210 },
-        PlantDefinition { name: "Jormungandr's Coil", seed: 21321 },
-        PlantDefinition { name: "Sleipnir's Stride", seed: 32432 },
-        PlantDefinition { name: "Huginn's Thought", seed: 43543 },
-        PlantDefinition { name: "Muninn's Memory", seed: 54654 },
-        PlantDefinition { name: "Gungnir's Point", seed: 65765 },
-        PlantDefinition { name: "Mjolnir's Might", seed: 76876 },
-        PlantDefinition { name: "Bifrost's Bridge", seed: 87987 },
-        PlantDefinition { name: "Yggdrasil's Root", seed: 98098 },
-        PlantDefinition { name: "Asgard's Pride", seed: 10113 },
-        PlantDefinition { name: "Midgard's Serpent", seed: 21224 },
-        PlantDefinition { name: "Jotunheim's Jotun", seed: 32335 },
-        PlantDefinition { name: "Vanaheim's Vanir", seed: 43446 },
-        PlantDefinition { name: "Alfheim's Elf", seed: 54557 },
-        PlantDefinition { name: "Svartalfheim's Dwarf", seed: 65668 },
-        PlantDefinition { name: "Muspelheim's Fire", seed: 76779 },
-        PlantDefinition { name: "Niflheim's Ice", seed: 87880 },
-        PlantDefinition { name: "Hel's Hand", seed: 98991 },
-        PlantDefinition { name: "Ragnarok's Ruin", seed: 10103 },
-        PlantDefinition { name: "Valhalla's Valor", seed: 20204 },
-        PlantDefinition { name: "Einherjar's Echo", seed: 30305 },
-        PlantDefinition { name: "Valkyrie's Voice", seed: 40406 },
-        PlantDefinition { name: "Norn's Thread", seed: 50507 },
-        PlantDefinition { name: "Fates' Decree", seed: 60608 },
-        PlantDefinition { name: "Chaos's Bloom", seed: 70709 },
-        PlantDefinition { name: "Order's Orchid", seed: 80810 },
-        PlantDefinition { name: "Light's Lily", seed: 90911 },
-        PlantDefinition { name: "Dark's Daisy", seed: 11113 },
-        PlantDefinition { name: "Sun's Sunflower", seed: 22224 },
-        PlantDefinition { name: "Moon's Morning Glory", seed: 33335 },
-        PlantDefinition { name: "Star's Snapdragon", seed: 44446 },
-        PlantDefinition { name: "Sky's Snowdrop", seed: 55557 },
-        PlantDefinition { name: "Earth's Elderflower", seed: 66668 },
-        PlantDefinition { name: "Sea's Sweet Pea", seed: 77779 },
-        PlantDefinition { name: "Fire's Foxglove", seed: 88880 },
-        PlantDefinition { name: "Wind's Wolfsbane", seed: 99991 },
-        PlantDefinition { name: "Storm's Snapdragon", seed: 10002 },
-        PlantDefinition { name: "Ice's Iris", seed: 20003 },
-        PlantDefinition { name: "Stone's Snapdragon", seed: 30004 },
-        PlantDefinition { name: "Wood's Wolfsbane", seed: 40005 },
-        PlantDefinition { name: "Metal's Marigold", seed: 50006 },
-        PlantDefinition { name: "Void's Violet", seed: 60007 },
-        PlantDefinition { name: "Aether's Azalea", seed: 70008 },
-        PlantDefinition { name: "Nether's Nettle", seed: 80009 },
-        PlantDefinition { name: "Dream's Dahlia", seed: 90010 },
-        PlantDefinition { name: "Nightmare's Nightshade", seed: 12322 },
-        PlantDefinition { name: "Memory's Mimosa", seed: 23433 },
-        PlantDefinition { name: "Thought's Thistle", seed: 34544 },
-        PlantDefinition { name: "Emotion's Elderflower", seed: 45655 },
-        PlantDefinition { name: "Soul's Sunflower", seed: 56766 },
-        PlantDefinition { name: "Spirit's Snapdragon", seed: 67877 },
-        PlantDefinition { name: "Heart's Hollyhock", seed: 78988 },
-        PlantDefinition { name: "Mind's Monkshood", seed: 89099 },
-        PlantDefinition { name: "Body's Bluebell", seed: 90110 },
-        PlantDefinition { name: "Life's Lily", seed: 10211 },
-        PlantDefinition { name: "Death's Daisy", seed: 21322 },
-        PlantDefinition { name: "Time's Thyme", seed: 32433 },
-        PlantDefinition { name: "Space's Snapdragon", seed: 43544 },
-        PlantDefinition { name: "Gravity's Gladiolus", seed: 54655 },
-        PlantDefinition { name: "Energy's Elderflower", seed: 65766 },
-        PlantDefinition { name: "Matter's Marigold", seed: 76877 },
-        PlantDefinition { name: "Antimatter's Anemone", seed: 87988 },
-        PlantDefinition { name: "Quantum's Quince", seed: 98099 },
-        PlantDefinition { name: "Singularity's Snapdragon", seed: 10114 },
-        PlantDefinition { name: "Event Horizon's Elderflower", seed: 21225 },
-        PlantDefinition { name: "Nebula's Nettle", seed: 32336 },
-        PlantDefinition { name: "Galaxy's Gladiolus", seed: 43447 },
-        PlantDefinition { name: "Universe's Uva-ursi", seed: 54558 },
-        PlantDefinition { name: "Multiverse's Monkshood", seed: 65669 },
-        PlantDefinition { name: "Dimension's Dahlia", seed: 76780 },
-        PlantDefinition { name: "Reality's Rhododendron", seed: 87881 },
-        PlantDefinition { name: "Illusion's Iris", seed: 98992 },
-        PlantDefinition { name: "Truth's Tulip", seed: 10104 },
-        PlantDefinition { name: "Lie's Lily", seed: 20205 },
-        PlantDefinition { name: "Hope's Hollyhock", seed: 30306 },
-        PlantDefinition { name: "Despair's Daisy", seed: 40407 },
-        PlantDefinition { name: "Joy's Jasmine", seed: 50508 },
-        PlantDefinition { name: "Sorrow's Snapdragon", seed: 60609 },
-        PlantDefinition { name: "Love's Lavender", seed: 70710 },
-        PlantDefinition { name: "Hate's Heather", seed: 80811 },
-        PlantDefinition { name: "Peace's Peony", seed: 90912 },
-        PlantDefinition { name: "War's Wolfsbane", seed: 11114 },
-        PlantDefinition { name: "Wisdom's Wisteria", seed: 22225 },
-        PlantDefinition { name: "Folly's Foxglove", seed: 33336 },
-        PlantDefinition { name: "Knowledge's Kohlrabi", seed: 44447 },
-        PlantDefinition { name: "Ignorance's Iris", seed: 55558 },
-        PlantDefinition { name: "Power's Poppy", seed: 66669 },
-        PlantDefinition { name: "Weakness's Wisteria", seed: 77780 },
-        PlantDefinition { name: "Courage's Crocus", seed: 88881 },
-        PlantDefinition { name: "Fear's Foxglove", seed: 99992 },
-        PlantDefinition { name: "Virtue's Violet", seed: 10003 },
-        PlantDefinition { name: "Sin's Snapdragon", seed: 20004 },
-        PlantDefinition { name: "Faith's Freesia", seed: 30005 },
-        PlantDefinition { name: "Doubt's Dahlia", seed: 40006 },
-        PlantDefinition { name: "Destiny's Daisy", seed: 50007 },
-        PlantDefinition { name: "Freedom's Foxglove", seed: 60008 },
-    ];
-
-    definitions.iter().map(|def| {
-        let mut rng = StdRng::seed_from_u64(def.seed);
-        Plant {
-            species: def.name.to_string(),
-            genetics: PlantGenetics {
-                growth_time: rng.gen_range(5..15),
-                yield_range: (rng.gen_range(1..5), rng.gen_range(5..10)),
-                ideal_moisture_range: (rng.gen_range(0.3..0.5), rng.gen_range(0.5..0.7)),
-                nutrient_consumption: (rng.gen_range(0.05..0.15), rng.gen_range(0.05..0.15), rng.gen_range(0.05..0.15)),
-                light_req: rng.gen_range(4.0..6.0),
-                pest_resistance: rng.gen_range(0.05..0.15),
-                disease_resistance: rng.gen_range(0.05..0.15),
-                genetic_stability: rng.gen_range(0.85..0.95),
-            },
-            life_cycle_stage: LifeCycleStage::Seed,
-            age: 0,
-            maturity_age: rng.gen_range(8..12),
-            wither_time: rng.gen_range(13..18),
-            growth_progress: 0.0,
-            health: 1.0,
-        }
-    }).collect()
-});
+/// The built-in species, used when no catalogue file is given.
+pub static SPECIES: Lazy<Vec<SpeciesDefinition>> =
+    Lazy::new(|| serde_json::from_str(BUILT_IN).expect("data/species.json is a valid catalogue"));
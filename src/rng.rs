@@ -0,0 +1,22 @@
+// src/rng.rs
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// The simulation RNG. Its full state is serialized with the save so a
+/// replay of the same commands from the same save is bit-identical.
+pub type GameRng = ChaCha8Rng;
+
+pub fn seeded(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed)
+}
+
+/// Picks a fresh seed for games started without `--seed` and for saves
+/// written before the RNG was part of the game state.
+pub fn random_seed() -> u64 {
+    rand::thread_rng().gen()
+}
+
+pub fn from_entropy() -> GameRng {
+    seeded(random_seed())
+}
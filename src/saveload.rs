@@ -1,21 +1,87 @@
+use crate::catalogue;
 use crate::garden;
+use crate::genome::Genome;
+use crate::lineage::Ancestor;
+use crate::plant;
 use std::fs;
 use std::fs::File;
-use std::io::Write;
+use std::io::{Error, ErrorKind, Write};
 
+/// Writes the game to `filename` atomically: the state goes to a temporary
+/// file next to it first, which is then renamed over the target, so an
+/// interrupted save never leaves a truncated file behind.
 pub fn save_game(game_state: &garden::MainGameState, filename: &str) -> std::io::Result<()> {
     let serialized = serde_json::to_string(game_state).unwrap();
-    let mut file = File::create(filename)?;
+    let temp_filename = format!("{}.tmp", filename);
+    let mut file = File::create(&temp_filename)?;
     file.write_all(serialized.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&temp_filename, filename)?;
     Ok(())
 }
 
 pub fn load_game(filename: &str) -> std::io::Result<garden::MainGameState> {
     let data = fs::read_to_string(filename)?;
-    let game_state = serde_json::from_str(&data).unwrap();
+    let mut game_state: garden::MainGameState = serde_json::from_str(&data).unwrap();
+    // A game played with its own catalogue brings it back
+    if let Some(definitions) = &game_state.catalogue {
+        catalogue::restore(definitions).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+    }
+    if let Some(species) = unknown_species(&game_state) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("the save has '{}', which the species catalogue in use does not define", species),
+        ));
+    }
+    // Plants from saves made before genomes existed breed true to what they already are
+    for plot in game_state.plots.values_mut() {
+        for plant in plot.grid.tiles.iter_mut().flatten().filter_map(|tile| tile.plant.as_mut()) {
+            if plant.genome.is_empty() {
+                plant.genome = Genome::uniform(&plant.genetics);
+            }
+            // ...and start their pedigree as if grown from bought seed
+            if plant.id == 0 {
+                plant.id = game_state.next_id;
+                game_state.next_id += 1;
+                game_state.pedigree.insert(plant.id, Ancestor::of_plant(plant, game_state.tick_counter));
+            }
+        }
+    }
+    // Trade any species added since the save, or everything for saves from before the catalogue
+    game_state.market.restock(&game_state.hybrids);
+    for lot in game_state.seed_lots.values_mut() {
+        // Lots from before seeds were rolled one by one self-pollinate their single genome
+        if lot.father.is_empty() {
+            lot.father = lot.mother.clone();
+        }
+        if !game_state.pedigree.contains_key(&lot.id) {
+            let genetics = plant::species_template(&game_state.hybrids, &lot.species)
+                .map(|template| lot.mother.express(&template.genetics));
+            if let Some(genetics) = genetics {
+                game_state.pedigree.insert(lot.id, Ancestor::of_seed_lot(lot, genetics));
+            }
+        }
+    }
     Ok(game_state)
 }
 
+/// The first species planted or kept as seed that neither the catalogue nor
+/// the save's hybrids define.
+fn unknown_species(game_state: &garden::MainGameState) -> Option<&str> {
+    let planted = game_state
+        .plots
+        .values()
+        .flat_map(|plot| plot.grid.tiles.iter().flatten())
+        .filter_map(|tile| tile.plant.as_ref())
+        .map(|plant| plant.species.as_str());
+    let seeds = game_state.seed_inventory.keys().map(String::as_str);
+    let lots = game_state.seed_lots.values().map(|lot| lot.species.as_str());
+    planted
+        .chain(seeds)
+        .chain(lots)
+        .find(|species| plant::species_template(&game_state.hybrids, species).is_none())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::engine;
@@ -24,7 +90,7 @@ mod tests {
     #[test]
     fn test_save_and_load() {
         let mut game_state = engine::new_game();
-        engine::plant_seed(&mut game_state, 0, 0, "tomato");
+        engine::plant_seed(&mut game_state, (0, 0), 0, 0, "tomato");
         let filename = "test_game.json";
 
         // Save the game
@@ -42,4 +108,166 @@ mod tests {
         // Clean up the test file
         std::fs::remove_file(filename).unwrap();
     }
+
+    #[test]
+    fn test_save_overwrites_without_leaving_temp_file() {
+        let mut game_state = engine::new_game();
+        let filename = "test_overwrite_game.json";
+
+        save_game(&game_state, filename).unwrap();
+        game_state.wallet = crate::money::Money::from_cents(4200);
+        save_game(&game_state, filename).unwrap();
+
+        let loaded_game_state = load_game(filename).unwrap();
+        assert_eq!(loaded_game_state.wallet, crate::money::Money::from_cents(4200));
+        assert!(!std::path::Path::new("test_overwrite_game.json.tmp").exists());
+
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_old_saves_get_a_genome_matching_their_plants() {
+        let mut game_state = engine::new_game();
+        game_state.seed_inventory.insert("Crimson Bloom".to_string(), 1);
+        engine::plant_seed(&mut game_state, (0, 0), 0, 0, "Crimson Bloom");
+        let mut json = serde_json::to_value(&game_state).unwrap();
+        let plant = &mut json["plots"][0][1]["grid"]["tiles"][0][0]["plant"];
+        plant.as_object_mut().unwrap().remove("genome");
+        let filename = "test_old_genome_game.json";
+        std::fs::write(filename, json.to_string()).unwrap();
+
+        let loaded_game_state = load_game(filename).unwrap();
+        std::fs::remove_file(filename).unwrap();
+
+        let plant = loaded_game_state.plots[&(0, 0)].grid.tiles[0][0].plant.as_ref().unwrap();
+        assert!(!plant.genome.is_empty());
+        assert_eq!(plant.genome.express(&plant.genetics).light_req, plant.genetics.light_req);
+    }
+
+    #[test]
+    fn test_old_saves_get_pedigree_ids() {
+        let mut game_state = engine::new_game();
+        game_state.seed_inventory.insert("Crimson Bloom".to_string(), 1);
+        engine::plant_seed(&mut game_state, (0, 0), 0, 0, "Crimson Bloom");
+        let mut json = serde_json::to_value(&game_state).unwrap();
+        let plant = &mut json["plots"][0][1]["grid"]["tiles"][0][0]["plant"];
+        plant.as_object_mut().unwrap().remove("id");
+        let state = json.as_object_mut().unwrap();
+        state.remove("pedigree");
+        let next_id = state.remove("next_id").unwrap();
+        state.insert("next_seed_lot_id".to_string(), next_id);
+        let filename = "test_old_pedigree_game.json";
+        std::fs::write(filename, json.to_string()).unwrap();
+
+        let loaded_game_state = load_game(filename).unwrap();
+        std::fs::remove_file(filename).unwrap();
+
+        let plant = loaded_game_state.plots[&(0, 0)].grid.tiles[0][0].plant.as_ref().unwrap();
+        assert_ne!(plant.id, 0);
+        assert!(loaded_game_state.pedigree.contains_key(&plant.id));
+        assert!(loaded_game_state.next_id > plant.id);
+    }
+
+    #[test]
+    fn test_old_saves_keep_their_float_wallet() {
+        let game_state = engine::new_game();
+        let mut json = serde_json::to_value(&game_state).unwrap();
+        let state = json.as_object_mut().unwrap();
+        state.insert("wallet".to_string(), serde_json::json!(87.65));
+        state.remove("ledger");
+        state.remove("fees");
+        let filename = "test_old_wallet_game.json";
+        std::fs::write(filename, json.to_string()).unwrap();
+
+        let loaded_game_state = load_game(filename).unwrap();
+        std::fs::remove_file(filename).unwrap();
+
+        assert_eq!(loaded_game_state.wallet, crate::money::Money::from_cents(8765));
+        assert!(loaded_game_state.ledger.transactions.is_empty());
+        assert_eq!(loaded_game_state.fees, crate::ledger::FeeSchedule::default());
+    }
+
+    #[test]
+    fn test_old_seed_lots_self_pollinate_their_genome() {
+        let mut game_state = engine::new_game();
+        game_state.seed_inventory.insert("Crimson Bloom".to_string(), 1);
+        engine::plant_seed(&mut game_state, (0, 0), 0, 0, "Crimson Bloom");
+        {
+            let plant = game_state.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].plant.as_mut().unwrap();
+            plant.life_cycle_stage = crate::plant::LifeCycleStage::Fruiting;
+            plant.genetics.yield_range = (4, 4);
+        }
+        engine::collect_seeds(&mut game_state, (0, 0), 0, 0);
+        let mut json = serde_json::to_value(&game_state).unwrap();
+        let lot = json["seed_lots"].as_object_mut().unwrap().values_mut().next().unwrap();
+        let lot = lot.as_object_mut().unwrap();
+        let genome = lot.remove("mother").unwrap();
+        lot.insert("genome".to_string(), genome);
+        lot.remove("father");
+        lot.remove("stability");
+        let filename = "test_old_seed_lot_game.json";
+        std::fs::write(filename, json.to_string()).unwrap();
+
+        let mut loaded_game_state = load_game(filename).unwrap();
+        std::fs::remove_file(filename).unwrap();
+
+        let (&lot_id, lot) = loaded_game_state.seed_lots.iter().next().unwrap();
+        assert!(!lot.mother.is_empty());
+        assert_eq!(lot.father, lot.mother);
+        assert!(engine::plant_seed_lot(&mut loaded_game_state, (0, 0), 1, 0, lot_id));
+    }
+
+    #[test]
+    fn test_saves_with_uncatalogued_species_are_refused() {
+        let mut game_state = engine::new_game();
+        game_state.seed_inventory.insert("Moon Turnip".to_string(), 3);
+        let filename = "test_uncatalogued_game.json";
+        save_game(&game_state, filename).unwrap();
+
+        let loaded = load_game(filename);
+        std::fs::remove_file(filename).unwrap();
+
+        let error = loaded.err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("Moon Turnip"));
+    }
+
+    #[test]
+    fn test_the_catalogue_a_game_was_played_with_is_restored() {
+        let mut game_state = engine::new_game();
+        assert!(serde_json::to_value(&game_state).unwrap().get("catalogue").is_none());
+
+        // The built-in species stand in for a catalogue file here, as the catalogue in use is process-wide
+        game_state.catalogue = Some(crate::plant_definitions::SPECIES.clone());
+        game_state.seed_inventory.insert("Crimson Bloom".to_string(), 2);
+        let filename = "test_catalogue_game.json";
+        save_game(&game_state, filename).unwrap();
+        let loaded = load_game(filename);
+        std::fs::remove_file(filename).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.catalogue.as_deref(), Some(&crate::plant_definitions::SPECIES[..]));
+        assert!(crate::catalogue::catalogue().get("Crimson Bloom").is_some());
+    }
+
+    #[test]
+    fn test_saves_from_another_catalogue_are_refused() {
+        // Make sure the built-in species are in use before the save offers its own
+        crate::catalogue::catalogue();
+        let mut game_state = engine::new_game();
+        let json = r#"[{"name": "Moon Turnip", "category": "vegetable", "maturity_age": 10, "wither_time": 15,
+            "growth_time": 8, "yield_range": [3, 8], "ideal_moisture_range": [0.4, 0.6],
+            "nutrient_consumption": [0.1, 0.1, 0.1], "light_req": 5.0, "pest_resistance": 0.1,
+            "disease_resistance": 0.1, "genetic_stability": 0.9}]"#;
+        game_state.catalogue = Some(serde_json::from_str(json).unwrap());
+        let filename = "test_other_catalogue_game.json";
+        save_game(&game_state, filename).unwrap();
+        let loaded = load_game(filename);
+        std::fs::remove_file(filename).unwrap();
+
+        let error = loaded.err().unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("different species catalogue"), "{}", error);
+        assert!(crate::catalogue::catalogue().get("Moon Turnip").is_none());
+    }
 }
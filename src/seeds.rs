@@ -0,0 +1,97 @@
+// src/seeds.rs
+
+use crate::breeding::Hybrid;
+use crate::economy;
+use crate::genome::Genome;
+use crate::plant;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A batch of seeds from one pollination: collected from a plant that
+/// pollinated itself, or bred from two. Every seed is its own individual,
+/// rolled from the parents' genomes when it is planted.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SeedLot {
+    pub id: u32,
+    pub species: String,
+    /// The seed parent's genome. Saves from before seeds were rolled one by
+    /// one kept the lot's single genome here.
+    #[serde(alias = "genome")]
+    pub mother: Genome,
+    /// The pollen parent's genome; the same as `mother` for collected seed.
+    #[serde(default)]
+    pub father: Genome,
+    /// The parents' average genetic stability, which sets how often a seed mutates.
+    #[serde(default = "fully_stable")]
+    pub stability: f32,
+    pub quantity: u32,
+    /// The tick the seeds were collected on.
+    pub collected_at: u64,
+    /// The plants the seeds came from: one when collected, two when bred.
+    #[serde(default)]
+    pub parents: Vec<u32>,
+    /// One more than the older parent's generation.
+    #[serde(default)]
+    pub generation: u32,
+}
+
+fn fully_stable() -> f32 {
+    1.0
+}
+
+impl SeedLot {
+    /// The genome of one seed: an allele from each of the mother's and the
+    /// father's pairs, mutated according to `stability`. Also returns how
+    /// many alleles mutated.
+    pub fn roll_seed(&self, rng: &mut impl Rng) -> (Genome, u32) {
+        let mut genome = Genome::cross(&self.mother, &self.father, rng);
+        let mutations = genome.mutate(self.stability, rng);
+        (genome, mutations)
+    }
+}
+
+/// Seeds collected per harvested plant, as a share of its yield; the rest is produce.
+pub const SEED_SHARE: f32 = 0.5;
+
+/// The error for planting a species with no bought seed left.
+pub fn no_seeds(species: &str) -> String {
+    format!(
+        "You have no {} seeds; buy some with `market buy \"{}\" <quantity>`",
+        species,
+        economy::seed_item(species)
+    )
+}
+
+/// A table of the bought seed in stock, by species.
+pub fn list_seed_inventory(seed_inventory: &BTreeMap<String, u32>) -> String {
+    let mut listing = String::from("Species\t\t\tQty\n");
+    for (species, quantity) in seed_inventory.iter().filter(|(_, quantity)| **quantity > 0) {
+        listing.push_str(&format!("{}\t\t{}\n", species, quantity));
+    }
+    listing
+}
+
+/// A table of the seed lots with any seeds left and the main traits of each
+/// lot's seed parent; the seeds themselves vary around them.
+pub fn list_seed_lots(seed_lots: &BTreeMap<u32, SeedLot>, hybrids: &BTreeMap<String, Hybrid>) -> String {
+    let mut listing = String::from("Lot\tGen\tQty\tSpecies\t\tYield\tLight\tPest res\tDisease res\tStability\n");
+    for lot in seed_lots.values().filter(|lot| lot.quantity > 0) {
+        let Some(template) = plant::species_template(hybrids, &lot.species) else { continue };
+        let genetics = lot.mother.express(&template.genetics);
+        listing.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}-{}\t{:.1}h\t{:.2}\t\t{:.2}\t\t{:.2}\n",
+            lot.id,
+            lot.generation,
+            lot.quantity,
+            lot.species,
+            genetics.yield_range.0,
+            genetics.yield_range.1,
+            genetics.light_req,
+            genetics.pest_resistance,
+            genetics.disease_resistance,
+            genetics.genetic_stability
+        ));
+    }
+    listing
+}
@@ -0,0 +1,191 @@
+// src/targeting.rs
+
+use crate::garden::{Plot, Tile};
+
+/// A span of tile indices along one axis. `end` is exclusive; `None` runs to the edge of the plot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisRange {
+    pub start: u32,
+    pub end: Option<u32>,
+}
+
+impl AxisRange {
+    pub const ALL: AxisRange = AxisRange { start: 0, end: None };
+
+    /// Just `index`. The last possible index runs to the edge, which no plot reaches.
+    pub fn single(index: u32) -> AxisRange {
+        AxisRange { start: index, end: index.checked_add(1) }
+    }
+
+    /// Parses `N`, `*`, `A..B`, `A..=B`, `A..` or `..B`.
+    pub fn parse(value: &str) -> Result<AxisRange, String> {
+        let value = value.trim();
+        if value == "*" {
+            return Ok(AxisRange::ALL);
+        }
+        let parse_index = |s: &str| {
+            s.trim()
+                .parse::<u32>()
+                .map_err(|_| format!("'{}' is not a valid tile index", s))
+        };
+        let after = |index: u32| index.checked_add(1).ok_or_else(|| format!("tile index {} is too large", index));
+        match value.split_once("..") {
+            None => {
+                let index = parse_index(value)?;
+                Ok(AxisRange { start: index, end: Some(after(index)?) })
+            }
+            Some((start, end)) => {
+                let start = if start.is_empty() { 0 } else { parse_index(start)? };
+                let end = match end.strip_prefix('=') {
+                    Some(end) => Some(after(parse_index(end)?)?),
+                    None if end.is_empty() => None,
+                    None => Some(parse_index(end)?),
+                };
+                Ok(AxisRange { start, end })
+            }
+        }
+    }
+
+    fn indices(&self, len: u32) -> std::ops::Range<u32> {
+        let end = self.end.map_or(len, |end| end.min(len));
+        self.start.min(end)..end
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    fn holds<T: PartialOrd>(self, left: T, right: T) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Equal => left == right,
+            Comparison::NotEqual => left != right,
+        }
+    }
+}
+
+/// A `--where` predicate such as `stage=fruiting` or `moisture<0.3`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileFilter {
+    key: String,
+    comparison: Comparison,
+    value: String,
+}
+
+const TEXT_KEYS: [&str; 5] = ["stage", "species", "pest", "disease", "soil"];
+const NUMERIC_KEYS: [&str; 12] = [
+    "moisture", "nitrogen", "n", "phosphorus", "p", "potassium", "k", "ph", "weeds", "health", "age", "growth",
+];
+
+impl TileFilter {
+    pub fn parse(expression: &str) -> Result<TileFilter, String> {
+        // Two-character operators first so that `<=` is not read as `<`.
+        let operators = [
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("!=", Comparison::NotEqual),
+            ("=", Comparison::Equal),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+        ];
+        let (key, comparison, value) = operators
+            .iter()
+            .find_map(|(op, comparison)| {
+                expression
+                    .split_once(op)
+                    .map(|(key, value)| (key.trim().to_lowercase(), *comparison, value.trim().to_string()))
+            })
+            .ok_or_else(|| format!("'{}' is not a filter like 'moisture<0.3'", expression))?;
+
+        if TEXT_KEYS.contains(&key.as_str()) {
+            if !matches!(comparison, Comparison::Equal | Comparison::NotEqual) {
+                return Err(format!("'{}' can only be compared with = or !=", key));
+            }
+        } else if NUMERIC_KEYS.contains(&key.as_str()) {
+            value
+                .parse::<f32>()
+                .map_err(|_| format!("'{}' is not a number in filter '{}'", value, expression))?;
+        } else {
+            return Err(format!("Unknown filter key '{}'", key));
+        }
+        Ok(TileFilter { key, comparison, value })
+    }
+
+    pub fn matches(&self, tile: &Tile) -> bool {
+        let plant = tile.plant.as_ref();
+        if TEXT_KEYS.contains(&self.key.as_str()) {
+            let actual = match self.key.as_str() {
+                "stage" => plant.map(|plant| format!("{:?}", plant.life_cycle_stage)),
+                "species" => plant.map(|plant| plant.species.clone()),
+                "soil" => Some(format!("{:?}", tile.soil.soil_type)),
+                // Only diseases that have shown symptoms can be targeted.
+                "disease" => tile
+                    .disease
+                    .as_ref()
+                    .filter(|disease| disease.is_visible())
+                    .map(|disease| format!("{:?}", disease.disease_type)),
+                _ => tile.pest.as_ref().map(|pest| format!("{:?}", pest.pest_type)),
+            }
+            .unwrap_or_else(|| "none".to_string());
+            return self.comparison.holds(actual.to_lowercase(), self.value.to_lowercase());
+        }
+
+        let nutrients = &tile.soil.soil_nutrients;
+        let actual = match self.key.as_str() {
+            "moisture" => Some(tile.soil.soil_moisture),
+            "nitrogen" | "n" => Some(nutrients.nitrogen),
+            "phosphorus" | "p" => Some(nutrients.phosphorus),
+            "potassium" | "k" => Some(nutrients.potassium),
+            "ph" => Some(tile.soil.soil_ph),
+            "weeds" => Some(tile.soil.weeds),
+            "health" => plant.map(|plant| plant.health),
+            "age" => plant.map(|plant| plant.age as f32),
+            _ => plant.map(|plant| plant.growth_progress),
+        };
+        // Plant properties never match an empty tile.
+        actual.is_some_and(|actual| self.comparison.holds(actual, self.value.parse().unwrap_or(0.0)))
+    }
+}
+
+/// The set of tiles a tile command fans out over: a rectangle plus optional filters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileSelection {
+    pub xs: AxisRange,
+    pub ys: AxisRange,
+    pub filters: Vec<TileFilter>,
+}
+
+impl TileSelection {
+    /// Whether the selection can only ever address one tile.
+    pub fn is_single(&self) -> bool {
+        self.filters.is_empty()
+            && [self.xs, self.ys]
+                .iter()
+                .all(|axis| axis.end == axis.start.checked_add(1))
+    }
+
+    /// The matching tiles of `plot`, as (x, y) in row-major order.
+    pub fn tiles(&self, plot: &Plot) -> Vec<(u32, u32)> {
+        let mut tiles = Vec::new();
+        for y in self.ys.indices(plot.height()) {
+            for x in self.xs.indices(plot.width()) {
+                let tile = &plot.grid.tiles[y as usize][x as usize];
+                if self.filters.iter().all(|filter| filter.matches(tile)) {
+                    tiles.push((x, y));
+                }
+            }
+        }
+        tiles
+    }
+}
@@ -1,11 +1,17 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
-    use crate::engine::{new_game, plant_seed, run_game_tick};
-    use crate::garden::{Grid, MainGameState, Nutrients, Soil, SoilType, Tile};
+    use crate::engine::{new_game, new_game_with_seed, plant_seed, run_game_tick};
+    use crate::garden::{AmendmentKind, Grid, MainGameState, Nutrients, Soil, SoilType, Tile};
     use crate::plant::LifeCycleStage;
     use crate::engine;
+    use crate::ledger::TransactionKind;
+    use crate::money::Money;
 
-    fn run_game_tick_without_pests(state: &mut MainGameState, weather: Option<crate::weather::Weather>) {
+    fn run_game_tick_without_pests(
+        state: &mut MainGameState,
+        weather: Option<crate::weather::Weather>,
+    ) -> Vec<engine::StageChange> {
         state.tick_counter += 1;
 
         if let Some(weather) = weather {
@@ -14,9 +20,10 @@ mod tests {
             engine::process_weather(state);
         }
         engine::process_environment(state);
-        engine::process_plants(state);
+        let changes = engine::process_plants(state);
 
-        crate::economy::update_market_prices(&mut state.market);
+        crate::economy::update_market_prices(&mut state.market, &mut state.rng);
+        changes
     }
 
     fn setup_test_game() -> MainGameState {
@@ -38,9 +45,16 @@ mod tests {
                         },
                         soil_ph: 7.0,
                         weeds: 0.0,
+                        amendment: None,
+                        pending_ph_shift: 0.0,
+                        mulch: 0,
+                        contamination: None,
+                        residue: 0.0,
                     },
                     plant: None,
                     pest: None,
+                    disease: None,
+                    beneficials: Default::default(),
                 });
             }
             tiles.push(row);
@@ -50,16 +64,31 @@ mod tests {
             crate::garden::Plot {
                 x: 0,
                 y: 0,
+                name: None,
                 grid: Grid { tiles },
             },
         );
+        // Plenty of seed of every species, so tests can plant freely
+        for species in crate::catalogue::catalogue().iter() {
+            game.seed_inventory.insert(species.template.species.clone(), 1000);
+        }
         game
     }
 
+    /// Plants `species` with exactly the species' template genetics, for
+    /// tests about growing conditions rather than individual variation.
+    fn plant_true_to_type(game: &mut MainGameState, x: u32, y: u32, species: &str) {
+        plant_seed(game, (0, 0), x, y, species);
+        let template = &crate::catalogue::catalogue().get(species).unwrap().template;
+        let plant = game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[y as usize][x as usize].plant.as_mut().unwrap();
+        plant.genome = crate::genome::Genome::uniform(&template.genetics);
+        plant.genetics = plant.genome.express(&template.genetics);
+    }
+
     #[test]
     fn test_plant_growth_ideal_conditions() {
         let mut game = setup_test_game();
-        plant_seed(&mut game, 0, 0, "Crimson Bloom");
+        plant_true_to_type(&mut game, 0, 0, "Crimson Bloom");
 
         let initial_age = game.plots[&(0, 0)].grid.tiles[0][0]
             .plant
@@ -136,10 +165,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fertilizer_amounts_cannot_be_negative() {
+        let mut game = setup_test_game();
+        let wallet = game.wallet;
+        assert!(!engine::fertilize_tile(&mut game, (0, 0), 0, 0, (-5.0, -5.0, -5.0)));
+        assert!(!engine::fertilize_tile(&mut game, (0, 0), 0, 0, (f32::NAN, 0.0, 0.0)));
+        assert_eq!(game.wallet, wallet, "A rejected mix must not credit the wallet.");
+        assert!(game.ledger.transactions.is_empty());
+    }
+
     #[test]
     fn test_plant_growth_non_ideal_moisture() {
         let mut game = setup_test_game();
-        plant_seed(&mut game, 0, 0, "Crimson Bloom");
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
 
         // Set moisture to a non-ideal level
         if let Some(plot) = game.plots.get_mut(&(0, 0)) {
@@ -164,7 +203,7 @@ mod tests {
     #[test]
     fn test_plant_life_cycle() {
         let mut game = setup_test_game();
-        plant_seed(&mut game, 0, 0, "Crimson Bloom");
+        plant_true_to_type(&mut game, 0, 0, "Crimson Bloom");
         game.current_weather = crate::weather::Weather::Sunny;
 
         // Check initial state
@@ -181,6 +220,7 @@ mod tests {
             if let Some(plot) = game.plots.get_mut(&(0, 0)) {
                 if let Some(tile) = plot.grid.tiles.get_mut(0).and_then(|row| row.get_mut(0)) {
                     tile.soil.soil_moisture = 0.5;
+                    tile.soil.soil_nutrients.nitrogen = 0.5;
                 }
             }
             run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
@@ -193,6 +233,7 @@ mod tests {
             if let Some(plot) = game.plots.get_mut(&(0, 0)) {
                 if let Some(tile) = plot.grid.tiles.get_mut(0).and_then(|row| row.get_mut(0)) {
                     tile.soil.soil_moisture = 0.5;
+                    tile.soil.soil_nutrients.nitrogen = 0.5;
                 }
             }
             run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
@@ -205,6 +246,7 @@ mod tests {
             if let Some(plot) = game.plots.get_mut(&(0, 0)) {
                 if let Some(tile) = plot.grid.tiles.get_mut(0).and_then(|row| row.get_mut(0)) {
                     tile.soil.soil_moisture = 0.5;
+                    tile.soil.soil_nutrients.nitrogen = 0.5;
                 }
             }
             run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
@@ -216,7 +258,7 @@ mod tests {
     #[test]
     fn test_plant_growth_heatwave() {
         let mut game = setup_test_game();
-        plant_seed(&mut game, 0, 0, "Crimson Bloom");
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
 
         // Set weather to heatwave
         game.current_weather = crate::weather::Weather::Heatwave;
@@ -237,15 +279,16 @@ mod tests {
     #[test]
     fn test_harvest() {
         let mut game = setup_test_game();
-        plant_seed(&mut game, 0, 0, "Crimson Bloom");
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
 
-        // Grow plant to maturity
+        // Grow plant to maturity, keeping it fed
         for _ in 0..15 {
+            game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].soil.soil_nutrients.nitrogen = 0.5;
             run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
         }
 
         // Harvest the plant
-        crate::engine::harvest(&mut game, 0, 0);
+        crate::engine::harvest(&mut game, (0, 0), 0, 0);
 
         // Check that the plant is removed
         assert!(game.plots[&(0, 0)].grid.tiles[0][0].plant.is_none());
@@ -253,4 +296,1486 @@ mod tests {
         // Check that the inventory has been updated
         assert!(game.inventory.get("Crimson Bloom").unwrap() > &0);
     }
+
+    #[test]
+    fn test_same_seed_replays_identically() {
+        let play = |seed: u64| {
+            let mut game = new_game_with_seed(seed);
+            plant_seed(&mut game, (0, 0), 2, 3, "Crimson Bloom");
+            plant_seed(&mut game, (0, 0), 4, 4, "Azure Fern");
+            for _ in 0..20 {
+                run_game_tick(&mut game, None);
+            }
+            crate::engine::harvest(&mut game, (0, 0), 2, 3);
+            serde_json::to_string(&game).unwrap()
+        };
+
+        assert_eq!(
+            play(42),
+            play(42),
+            "Replaying the same commands from the same seed should give identical saves."
+        );
+        assert_ne!(play(42), play(43), "Different seeds should produce different games.");
+    }
+
+    #[test]
+    fn test_rng_state_survives_save_round_trip() {
+        let mut game = new_game_with_seed(7);
+        run_game_tick(&mut game, None);
+        let mut reloaded: MainGameState =
+            serde_json::from_str(&serde_json::to_string(&game).unwrap()).unwrap();
+
+        for _ in 0..5 {
+            run_game_tick(&mut game, None);
+            run_game_tick(&mut reloaded, None);
+        }
+
+        assert_eq!(
+            serde_json::to_string(&game).unwrap(),
+            serde_json::to_string(&reloaded).unwrap(),
+            "A reloaded save should continue the same random stream."
+        );
+    }
+
+    #[test]
+    fn test_advance_reports_stage_changes() {
+        let mut game = setup_test_game();
+        plant_true_to_type(&mut game, 1, 1, "Crimson Bloom");
+
+        let summary = engine::advance(&mut game, 3, Some(crate::weather::Weather::Sunny));
+
+        assert_eq!(game.tick_counter, 3);
+        assert_eq!(summary.ticks.len(), 3);
+        let sprouted = summary.ticks[0]
+            .stage_changes
+            .iter()
+            .any(|change| (change.x, change.y) == (1, 1) && change.stage == LifeCycleStage::Sprout);
+        assert!(sprouted, "The first tick should report the seed sprouting.");
+        assert_eq!(summary.price_moves.len(), game.market.listings.len());
+    }
+
+    #[test]
+    fn test_buy_land_and_plant_on_it() {
+        let mut game = setup_test_game();
+        let wallet = game.wallet;
+
+        let cost = engine::buy_land(&mut game, (10, 0), (3, 2), Some("east".to_string())).unwrap();
+        assert_eq!(cost, engine::LAND_PRICE_PER_TILE.times(3 * 2));
+        assert_eq!(game.wallet, wallet - cost);
+
+        let east = crate::garden::resolve_plot(&game, Some("east")).unwrap();
+        assert_eq!(east, (10, 0));
+        assert_eq!(crate::garden::resolve_plot(&game, Some("10,0")).unwrap(), east);
+
+        plant_seed(&mut game, east, 2, 1, "Crimson Bloom");
+        assert!(game.plots[&east].grid.tiles[1][2].plant.is_some());
+        assert!(game.plots[&(0, 0)].grid.tiles[1][2].plant.is_none());
+
+        // Pests and growth must stay within each plot even when plot sizes differ.
+        for _ in 0..10 {
+            run_game_tick(&mut game, None);
+        }
+    }
+
+    #[test]
+    fn test_buy_land_rejects_overlap_and_insufficient_funds() {
+        let mut game = setup_test_game();
+        assert!(engine::buy_land(&mut game, (9, 9), (2, 2), None).is_err());
+        assert!(engine::buy_land(&mut game, (10, 10), (100, 100), None).is_err());
+        assert_eq!(game.plots.len(), 1);
+        assert!(crate::garden::resolve_plot(&game, Some("nowhere")).is_err());
+    }
+
+    #[test]
+    fn test_buy_land_rejects_huge_plots_without_overflowing() {
+        let mut game = setup_test_game();
+        assert!(engine::buy_land(&mut game, (100, 100), (70_000, 70_000), None).is_err());
+        assert!(engine::buy_land(&mut game, (i32::MAX - 1, 0), (5, 5), None).is_err());
+        assert!(!game.plots[&(0, 0)].overlaps(i32::MAX - 1, i32::MAX - 1, u32::MAX, u32::MAX));
+        assert_eq!(game.plots.len(), 1);
+    }
+
+    #[test]
+    fn test_range_targeting_fans_out_with_costs() {
+        use crate::targeting::{AxisRange, TileSelection};
+
+        let mut game = setup_test_game();
+        let selection = TileSelection {
+            xs: AxisRange::parse("3..7").unwrap(),
+            ys: AxisRange::parse("2..=4").unwrap(),
+            filters: Vec::new(),
+        };
+        let wallet = game.wallet;
+
+        let summary = engine::apply_to_selection(&mut game, (0, 0), &selection, engine::water_tile);
+
+        assert_eq!(summary.targeted, 4 * 3);
+        assert_eq!(summary.applied, 4 * 3);
+        assert_eq!(summary.cost, engine::WATER_COST.times(12));
+        assert_eq!(game.wallet, wallet - summary.cost);
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        assert!(tiles[2][3].soil.soil_moisture > 0.5);
+        assert!(tiles[4][6].soil.soil_moisture > 0.5);
+        assert_eq!(tiles[5][6].soil.soil_moisture, 0.5);
+        assert_eq!(tiles[2][7].soil.soil_moisture, 0.5);
+    }
+
+    #[test]
+    fn test_tile_indices_at_the_top_of_the_range_do_not_overflow() {
+        use crate::targeting::{AxisRange, TileSelection};
+
+        assert!(AxisRange::parse(&u32::MAX.to_string()).unwrap_err().contains("too large"));
+        assert!(AxisRange::parse(&format!("0..={}", u32::MAX)).unwrap_err().contains("too large"));
+        assert!(AxisRange::parse(&format!("0..{}", u32::MAX)).is_ok());
+
+        let mut game = setup_test_game();
+        let selection = TileSelection {
+            xs: AxisRange::single(u32::MAX),
+            ys: AxisRange::single(0),
+            filters: Vec::new(),
+        };
+        assert!(selection.is_single());
+        let summary = engine::apply_to_selection(&mut game, (0, 0), &selection, engine::water_tile);
+        assert_eq!((summary.applied, summary.cost), (0, Money::ZERO), "The tile is off the plot, not a crash.");
+    }
+
+    #[test]
+    fn test_where_filters_select_matching_tiles() {
+        use crate::targeting::{AxisRange, TileFilter, TileSelection};
+
+        let mut game = setup_test_game();
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[1][1].soil.soil_moisture = 0.2;
+        plant_seed(&mut game, (0, 0), 5, 5, "Crimson Bloom");
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[5][5].plant.as_mut().unwrap().life_cycle_stage =
+            LifeCycleStage::Fruiting;
+        plant_seed(&mut game, (0, 0), 6, 5, "Crimson Bloom");
+
+        let select = |filter: &str| TileSelection {
+            xs: AxisRange::ALL,
+            ys: AxisRange::ALL,
+            filters: vec![TileFilter::parse(filter).unwrap()],
+        };
+        let plot = &game.plots[&(0, 0)];
+        assert_eq!(select("moisture<0.3").tiles(plot), vec![(1, 1)]);
+        assert_eq!(select("stage=fruiting").tiles(plot), vec![(5, 5)]);
+        assert_eq!(select("stage!=none").tiles(plot).len(), 2);
+        assert!(TileFilter::parse("colour=red").is_err());
+        assert!(TileFilter::parse("stage<fruiting").is_err());
+
+        let summary = engine::apply_to_selection(&mut game, (0, 0), &select("stage=fruiting"), engine::harvest);
+        assert_eq!((summary.targeted, summary.applied), (1, 1));
+        assert!(game.plots[&(0, 0)].grid.tiles[5][5].plant.is_none());
+        assert!(game.plots[&(0, 0)].grid.tiles[5][6].plant.is_some());
+    }
+
+    #[test]
+    fn test_plant_passes_through_mature_before_fruiting() {
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+
+        let mut stages = Vec::new();
+        for _ in 0..20 {
+            let tile = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0];
+            tile.soil.soil_moisture = 0.5;
+            tile.soil.soil_nutrients.nitrogen = 0.5;
+            let summary = run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
+            for change in summary {
+                stages.push(change.stage);
+            }
+        }
+
+        let mature = stages.iter().position(|stage| *stage == LifeCycleStage::Mature);
+        let fruiting = stages.iter().position(|stage| *stage == LifeCycleStage::Fruiting);
+        assert!(mature.is_some(), "The plant should become Mature.");
+        assert!(mature < fruiting, "Mature should come before Fruiting: {:?}", stages);
+    }
+
+    #[test]
+    fn test_unhealthy_plant_dies_and_must_be_cleared() {
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].plant.as_mut().unwrap().health = 0.01;
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Heatwave));
+
+        let plant = game.plots[&(0, 0)].grid.tiles[0][0].plant.as_ref().unwrap();
+        assert_eq!(plant.life_cycle_stage, LifeCycleStage::Dead);
+        assert!(!plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom"), "A dead plant blocks the tile.");
+        assert!(!engine::harvest(&mut game, (0, 0), 0, 0));
+
+        assert!(engine::clear_tile(&mut game, (0, 0), 0, 0));
+        assert!(plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom"));
+    }
+
+    #[test]
+    fn test_health_recovers_in_good_conditions() {
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].plant.as_mut().unwrap().health = 0.5;
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Cloudy));
+
+        let plant = game.plots[&(0, 0)].grid.tiles[0][0].plant.as_ref().unwrap();
+        assert!(plant.health > 0.5, "Health should recover in ideal conditions.");
+    }
+
+    #[test]
+    fn test_withered_plant_decays_into_nutrients() {
+        let mut game = setup_test_game();
+        plant_true_to_type(&mut game, 0, 0, "Crimson Bloom");
+        {
+            let tile = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0];
+            let plant = tile.plant.as_mut().unwrap();
+            plant.age = plant.wither_time + engine::DECAY_AGE - 1;
+            plant.life_cycle_stage = LifeCycleStage::Withering;
+            tile.soil.soil_nutrients.phosphorus = 0.0;
+        }
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
+
+        let tile = &game.plots[&(0, 0)].grid.tiles[0][0];
+        assert!(tile.plant.is_none(), "The withered plant should have decayed.");
+        assert_eq!(tile.soil.soil_nutrients.phosphorus, engine::DECAY_NUTRIENT_RETURN);
+    }
+
+    #[test]
+    fn test_generated_soil_comes_in_patches() {
+        use crate::garden::{create_grid, MAX_PLOT_SIDE};
+
+        let mut rng = crate::rng::seeded(3);
+        let grid = create_grid(MAX_PLOT_SIDE, 40, &mut rng);
+        let soils: Vec<SoilType> = grid.tiles.iter().flatten().map(|tile| tile.soil.soil_type).collect();
+        assert!([SoilType::Sand, SoilType::Clay, SoilType::Loam].iter().all(|soil| soils.contains(soil)));
+        let neighbours = grid.tiles.iter().flat_map(|row| row.windows(2));
+        let (same, pairs) = neighbours.fold((0, 0), |(same, pairs), pair| {
+            (same + (pair[0].soil.soil_type == pair[1].soil.soil_type) as u32, pairs + 1)
+        });
+        assert!(same * 3 > pairs * 2, "Most tiles should share their neighbour's soil.");
+    }
+
+    #[test]
+    fn test_soil_types_retain_water_differently() {
+        let mut game = setup_test_game();
+        {
+            let tiles = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles;
+            tiles[0][0].soil.soil_type = SoilType::Sand;
+            tiles[0][1].soil.soil_type = SoilType::Loam;
+            tiles[0][2].soil.soil_type = SoilType::Clay;
+        }
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
+
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        let (sand, loam, clay) = (
+            tiles[0][0].soil.soil_moisture,
+            tiles[0][1].soil.soil_moisture,
+            tiles[0][2].soil.soil_moisture,
+        );
+        assert!(sand < loam && loam < clay, "Sand should dry fastest and clay slowest: {} {} {}", sand, loam, clay);
+        assert!((loam - 0.45).abs() < 1e-4, "Loam keeps the baseline evaporation.");
+    }
+
+    #[test]
+    fn test_sand_drains_waterlogged_soil_faster_than_clay() {
+        let mut game = setup_test_game();
+        {
+            let tiles = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles;
+            tiles[0][0].soil.soil_type = SoilType::Sand;
+            tiles[0][0].soil.soil_moisture = 1.0;
+            tiles[0][1].soil.soil_type = SoilType::Clay;
+            tiles[0][1].soil.soil_moisture = 1.0;
+        }
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Cloudy));
+
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        assert!(tiles[0][0].soil.soil_moisture < tiles[0][1].soil.soil_moisture);
+        assert!(tiles[0][1].soil.soil_moisture > crate::garden::FIELD_CAPACITY);
+    }
+
+    #[test]
+    fn test_soil_amendment_shifts_type_over_time() {
+        let mut game = setup_test_game();
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].soil.soil_type = SoilType::Clay;
+
+        assert!(engine::amend_soil(&mut game, (0, 0), 0, 0, AmendmentKind::Compost));
+        assert!(
+            !engine::amend_soil(&mut game, (0, 0), 0, 0, AmendmentKind::Sand),
+            "A tile can only take one amendment at a time."
+        );
+        assert!(!engine::amend_soil(&mut game, (0, 0), 1, 0, AmendmentKind::Compost), "Compost does not change loam.");
+
+        for _ in 0..engine::AMENDMENT_TICKS - 1 {
+            run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Cloudy));
+        }
+        assert_eq!(game.plots[&(0, 0)].grid.tiles[0][0].soil.soil_type, SoilType::Clay);
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Cloudy));
+        let soil = &game.plots[&(0, 0)].grid.tiles[0][0].soil;
+        assert_eq!(soil.soil_type, SoilType::Loam);
+        assert!(soil.amendment.is_none());
+    }
+
+    #[test]
+    fn test_new_grids_mix_soil_types() {
+        let mut rng = crate::rng::seeded(1);
+        let grid = crate::garden::create_grid(20, 20, &mut rng);
+        let mut types: Vec<SoilType> = Vec::new();
+        for tile in grid.tiles.iter().flatten() {
+            if !types.contains(&tile.soil.soil_type) {
+                types.push(tile.soil.soil_type);
+            }
+        }
+        assert!(types.len() > 1, "A large grid should contain more than one soil type.");
+    }
+
+    #[test]
+    fn test_soil_ph_outside_preferred_range_slows_growth() {
+        let mut game = setup_test_game();
+        plant_true_to_type(&mut game, 0, 0, "Crimson Bloom");
+        plant_true_to_type(&mut game, 1, 0, "Crimson Bloom");
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][1].soil.soil_ph = 4.5;
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
+
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        let (good, acidic) = (tiles[0][0].plant.as_ref().unwrap(), tiles[0][1].plant.as_ref().unwrap());
+        assert_eq!(good.age, 1);
+        assert_eq!(acidic.age, 0, "Acidic soil should lock up nutrients and slow growth.");
+        assert!(
+            tiles[0][1].soil.soil_nutrients.phosphorus > tiles[0][0].soil.soil_nutrients.phosphorus,
+            "A plant in acidic soil should take up less phosphorus."
+        );
+
+        let (n, p, k) = good.genetics.nutrient_availability(4.5);
+        assert!(p < n && n < 1.0 && k > n, "Phosphorus should lock up fastest and potassium slowest.");
+    }
+
+    #[test]
+    fn test_lime_and_sulfur_shift_ph_gradually() {
+        let mut game = setup_test_game();
+        assert!(engine::adjust_ph(&mut game, (0, 0), 0, 0, true));
+        assert!(engine::adjust_ph(&mut game, (0, 0), 1, 0, false));
+        assert_eq!(game.plots[&(0, 0)].grid.tiles[0][0].soil.soil_ph, 7.0, "Lime takes time to work in.");
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Cloudy));
+        let ph = game.plots[&(0, 0)].grid.tiles[0][0].soil.soil_ph;
+        assert!(ph > 7.0 && ph < 7.0 + engine::PH_AMENDMENT_SHIFT);
+
+        for _ in 0..10 {
+            run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Cloudy));
+        }
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        assert!((tiles[0][0].soil.soil_ph - (7.0 + engine::PH_AMENDMENT_SHIFT)).abs() < 1e-4);
+        assert!((tiles[0][1].soil.soil_ph - (7.0 - engine::PH_AMENDMENT_SHIFT)).abs() < 1e-4);
+        assert_eq!(tiles[0][0].soil.pending_ph_shift, 0.0);
+    }
+
+    #[test]
+    fn test_weeds_grow_compete_and_spread() {
+        let mut game = setup_test_game();
+        {
+            let tiles = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles;
+            tiles[5][5].soil.weeds = 0.9;
+            tiles[0][0].soil.weeds = 0.2;
+        }
+
+        engine::process_weeds(&mut game);
+
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        assert!(tiles[0][0].soil.weeds > 0.2, "Weeds should grow.");
+        assert!(tiles[0][0].soil.soil_moisture < 0.5, "Weeds should draw moisture.");
+        assert!(tiles[0][0].soil.soil_nutrients.nitrogen < 0.5, "Weeds should draw nutrients.");
+
+        for _ in 0..30 {
+            engine::process_weeds(&mut game);
+        }
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        let neighbours = [tiles[4][5].soil.weeds, tiles[6][5].soil.weeds, tiles[5][4].soil.weeds, tiles[5][6].soil.weeds];
+        assert!(neighbours.iter().any(|weeds| *weeds > 0.0), "A dense weed patch should spread.");
+    }
+
+    #[test]
+    fn test_weeding_with_mulch_suppresses_regrowth() {
+        let mut game = setup_test_game();
+        {
+            let tiles = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles;
+            tiles[0][0].soil.weeds = 0.6;
+            tiles[0][1].soil.weeds = 0.6;
+        }
+
+        assert!(engine::weed_tile(&mut game, (0, 0), 0, 0, true));
+        assert!(engine::weed_tile(&mut game, (0, 0), 1, 0, false));
+        assert!(!engine::weed_tile(&mut game, (0, 0), 2, 0, false), "Nothing to weed.");
+        {
+            let tiles = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles;
+            assert_eq!(tiles[0][0].soil.mulch, engine::MULCH_TICKS);
+            // Let a few weeds back in on both tiles.
+            tiles[0][0].soil.weeds = 0.1;
+            tiles[0][1].soil.weeds = 0.1;
+        }
+
+        for _ in 0..5 {
+            engine::process_weeds(&mut game);
+        }
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        assert!(tiles[0][0].soil.weeds < tiles[0][1].soil.weeds, "Mulch should slow weed regrowth.");
+    }
+
+    #[test]
+    fn test_weeds_among_plants_are_marked() {
+        use crate::tui::{weed_marker, weed_symbol};
+
+        assert_eq!((weed_symbol(0.0), weed_marker(0.0)), ('.', ' '));
+        assert_eq!((weed_symbol(0.2), weed_marker(0.2)), (',', ','));
+        assert_eq!((weed_symbol(0.7), weed_marker(0.7)), ('w', 'w'));
+    }
+
+    #[test]
+    fn test_weeds_slow_plant_growth() {
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].soil.weeds = 1.0;
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
+
+        assert_eq!(game.plots[&(0, 0)].grid.tiles[0][0].plant.as_ref().unwrap().age, 0);
+    }
+
+    #[test]
+    fn test_nutrient_deficiencies_affect_growth_yield_and_report() {
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        plant_seed(&mut game, (0, 0), 1, 0, "Crimson Bloom");
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][1].soil.soil_nutrients.nitrogen = 0.0;
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Cloudy));
+
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        let (fed, starved) = (tiles[0][0].plant.as_ref().unwrap(), tiles[0][1].plant.as_ref().unwrap());
+        assert_eq!(fed.deficiency.worst(), 0.0);
+        assert_eq!(starved.deficiency.nitrogen, 1.0);
+        assert_eq!(starved.age, 0, "Nitrogen shortage should slow growth.");
+        assert!(starved.health < fed.health, "Deficiency should cost health.");
+
+        let report = engine::inspect_tile(&game, (0, 0), 1, 0).unwrap();
+        assert!(report.contains("severe N"), "{}", report);
+        assert!(engine::inspect_tile(&game, (0, 0), 0, 0).unwrap().contains("deficiency: none"));
+    }
+
+    #[test]
+    fn test_phosphorus_deficiency_cuts_yield() {
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        {
+            let plant = game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].plant.as_mut().unwrap();
+            plant.life_cycle_stage = LifeCycleStage::Fruiting;
+            plant.genetics.yield_range = (10, 10);
+            plant.deficiency.phosphorus = 1.0;
+        }
+
+        assert!(engine::harvest(&mut game, (0, 0), 0, 0));
+
+        let expected = (10.0 * (1.0 - engine::PHOSPHORUS_YIELD_PENALTY)).round() as u32;
+        assert_eq!(game.inventory["Crimson Bloom"], expected);
+    }
+
+    #[test]
+    fn test_cloudy_weather_and_winter_reduce_light() {
+        use crate::light::sunlight_hours;
+        use crate::weather::{Season, Weather};
+
+        assert!(sunlight_hours(Weather::Cloudy, Season::Spring) < sunlight_hours(Weather::Sunny, Season::Spring));
+        assert!(sunlight_hours(Weather::Sunny, Season::Winter) < sunlight_hours(Weather::Sunny, Season::Summer));
+        assert_eq!(Season::from_tick(0), Season::Spring);
+        assert_eq!(Season::from_tick(crate::weather::TICKS_PER_SEASON * 3), Season::Winter);
+
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].plant.as_mut().unwrap().genetics.light_req = 6.0;
+
+        run_game_tick_without_pests(&mut game, Some(Weather::Rainy));
+
+        assert_eq!(
+            game.plots[&(0, 0)].grid.tiles[0][0].plant.as_ref().unwrap().age,
+            0,
+            "A sun-loving plant should grow slower on a dull day."
+        );
+    }
+
+    #[test]
+    fn test_tall_plants_shade_short_neighbours() {
+        let mut game = setup_test_game();
+        plant_true_to_type(&mut game, 5, 5, "Crimson Bloom");
+        for (x, y) in [(4, 5), (6, 5), (5, 4), (5, 6)] {
+            plant_true_to_type(&mut game, x, y, "Crimson Bloom");
+            let plant = game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[y as usize][x as usize].plant.as_mut().unwrap();
+            plant.genetics.height = 10.0;
+        }
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[5][5].plant.as_mut().unwrap().genetics.height = 0.2;
+
+        let light = crate::light::light_levels(
+            &game.plots[&(0, 0)],
+            crate::weather::Weather::Sunny,
+            crate::weather::Season::Spring,
+        );
+        assert!(light[5][5] < light[0][0] * 0.5, "A short plant ringed by tall ones should be in shade.");
+        assert!(light[5][4] > light[5][5], "The tall plants themselves get more light.");
+
+        run_game_tick_without_pests(&mut game, Some(crate::weather::Weather::Sunny));
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        assert_eq!(tiles[5][5].plant.as_ref().unwrap().age, 0, "The shaded plant should grow slower.");
+        assert_eq!(tiles[5][4].plant.as_ref().unwrap().age, 1);
+    }
+
+    #[test]
+    fn test_the_sun_moves_across_the_day() {
+        let mut game = setup_test_game();
+        // Short plants with a tall one to the south of one and to the north of the other
+        for (x, y, height) in [(2, 2, 0.2), (2, 3, 10.0), (6, 3, 10.0), (6, 4, 0.2)] {
+            plant_true_to_type(&mut game, x, y, "Crimson Bloom");
+            let plant = game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[y as usize][x as usize].plant.as_mut().unwrap();
+            plant.genetics.height = height;
+        }
+
+        let light = crate::light::light_levels(
+            &game.plots[&(0, 0)],
+            crate::weather::Weather::Sunny,
+            crate::weather::Season::Spring,
+        );
+        assert!(light[2][2] < light[4][6], "A tall plant to the south takes the midday sun.");
+        assert!(light[4][6] < light[0][0], "One to the north still shades in the morning and evening.");
+    }
+
+    #[test]
+    fn test_pest_spawn_conditions_differ_by_type() {
+        use crate::pests::PestType;
+        use crate::weather::Weather;
+
+        let mut game = setup_test_game();
+        let soil = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].soil;
+        assert!(
+            PestType::SpiderMites.spawn_chance(Weather::Heatwave, soil)
+                > PestType::SpiderMites.spawn_chance(Weather::Rainy, soil) * 10.0
+        );
+        let lean = PestType::Aphids.spawn_chance(Weather::Sunny, soil);
+        soil.soil_nutrients.nitrogen = 1.0;
+        assert!(PestType::Aphids.spawn_chance(Weather::Sunny, soil) > lean, "Aphids favour lush, high-N plants.");
+    }
+
+    #[test]
+    fn test_pest_resistance_reduces_damage() {
+        use crate::pests::{Pest, PestType};
+
+        let mut game = setup_test_game();
+        game.current_weather = crate::weather::Weather::Cloudy;
+        for x in [0, 5] {
+            plant_seed(&mut game, (0, 0), x, 0, "Crimson Bloom");
+            let tile = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][x as usize];
+            tile.plant.as_mut().unwrap().genetics.pest_resistance = if x == 0 { 0.0 } else { 0.9 };
+            tile.pest = Some(Pest { pest_type: PestType::SpiderMites, infestation_level: 0.5 });
+        }
+
+        engine::process_pests(&mut game);
+
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        let (weak, resistant) = (tiles[0][0].plant.as_ref().unwrap(), tiles[0][5].plant.as_ref().unwrap());
+        assert!(resistant.health > weak.health, "Resistant plants should take less damage.");
+    }
+
+    #[test]
+    fn test_pests_die_out_without_a_host() {
+        use crate::pests::{Pest, PestType};
+
+        let mut game = setup_test_game();
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].pest =
+            Some(Pest { pest_type: PestType::Aphids, infestation_level: 0.5 });
+
+        engine::process_pests(&mut game);
+
+        assert!(game.plots[&(0, 0)].grid.tiles[0][0].pest.is_none());
+    }
+
+    #[test]
+    fn test_disease_incubates_before_symptoms_and_damage() {
+        use crate::disease::{Disease, DiseaseType};
+
+        let mut game = setup_test_game();
+        game.current_weather = crate::weather::Weather::Sunny;
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        {
+            let tile = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0];
+            tile.plant.as_mut().unwrap().genetics.disease_resistance = 0.0;
+            tile.disease = Some(Disease::new(DiseaseType::Blight));
+        }
+
+        for _ in 0..DiseaseType::Blight.profile().incubation_ticks - 1 {
+            assert!(engine::process_diseases(&mut game).is_empty());
+        }
+        assert!(!engine::inspect_tile(&game, (0, 0), 0, 0).unwrap().contains("Blight"), "Incubating diseases are hidden.");
+        let sightings = engine::process_diseases(&mut game);
+        assert_eq!(sightings.len(), 1);
+        assert_eq!(sightings[0].disease_type, DiseaseType::Blight);
+        assert!(engine::inspect_tile(&game, (0, 0), 0, 0).unwrap().contains("dark lesions"));
+
+        engine::process_diseases(&mut game);
+        assert!(game.plots[&(0, 0)].grid.tiles[0][0].plant.as_ref().unwrap().health < 1.0);
+    }
+
+    #[test]
+    fn test_disease_conditions_favour_wet_and_crowded_tiles() {
+        use crate::disease::DiseaseType;
+        use crate::weather::Weather;
+
+        let mut game = setup_test_game();
+        let soil = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].soil;
+        assert_eq!(DiseaseType::Blight.infection_chance(Weather::Heatwave, soil, 0.0), 0.0);
+        assert!(
+            DiseaseType::PowderyMildew.infection_chance(Weather::Cloudy, soil, 1.0)
+                > DiseaseType::PowderyMildew.infection_chance(Weather::Cloudy, soil, 0.0)
+        );
+        assert_eq!(DiseaseType::RootRot.infection_chance(Weather::Rainy, soil, 0.0), 0.0);
+        soil.soil_moisture = 1.0;
+        soil.soil_type = SoilType::Clay;
+        let clay = DiseaseType::RootRot.infection_chance(Weather::Rainy, soil, 0.0);
+        soil.soil_type = SoilType::Sand;
+        assert!(clay > DiseaseType::RootRot.infection_chance(Weather::Rainy, soil, 0.0));
+    }
+
+    #[test]
+    fn test_fungicide_and_removal_treat_disease() {
+        use crate::disease::{Disease, DiseaseType};
+        use crate::treatment::Treatment;
+
+        let mut game = setup_test_game();
+        game.current_weather = crate::weather::Weather::Sunny;
+        for (x, disease_type) in [(0, DiseaseType::Blight), (5, DiseaseType::RootRot)] {
+            plant_seed(&mut game, (0, 0), x, 0, "Crimson Bloom");
+            let tile = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][x as usize];
+            tile.disease = Some(Disease { incubation: 0, ..Disease::new(disease_type) });
+        }
+        engine::process_diseases(&mut game);
+
+        let selection = crate::targeting::TileSelection {
+            xs: crate::targeting::AxisRange::ALL,
+            ys: crate::targeting::AxisRange::ALL,
+            filters: vec![crate::targeting::TileFilter::parse("disease=blight").unwrap()],
+        };
+        let summary = engine::apply_to_selection(&mut game, (0, 0), &selection, |state, plot, x, y| {
+            engine::apply_treatment(state, plot, x, y, Treatment::Fungicide)
+        });
+        assert_eq!(summary.applied, 1);
+        assert!(game.plots[&(0, 0)].grid.tiles[0][0].disease.is_none(), "Fungicide cures blight.");
+
+        let wallet = game.wallet;
+        assert!(!engine::apply_treatment(&mut game, (0, 0), 5, 0, Treatment::Fungicide));
+        assert!(game.plots[&(0, 0)].grid.tiles[0][5].disease.is_some(), "Fungicide cannot reach root rot.");
+        assert_eq!(game.wallet, wallet, "A treatment that cannot work is not paid for.");
+        assert!(engine::clear_tile(&mut game, (0, 0), 5, 0));
+        let soil = &game.plots[&(0, 0)].grid.tiles[0][5].soil;
+        assert_eq!(soil.contamination.as_ref().unwrap().disease_type, DiseaseType::RootRot);
+    }
+
+    #[test]
+    fn test_treatments_target_specific_pests_with_partial_efficacy() {
+        use crate::pests::{Pest, PestType};
+        use crate::treatment::Treatment;
+
+        let mut game = setup_test_game();
+        game.current_weather = crate::weather::Weather::Sunny;
+        for (x, pest_type) in [(0, PestType::Aphids), (1, PestType::Whiteflies), (2, PestType::Aphids)] {
+            plant_seed(&mut game, (0, 0), x, 0, "Crimson Bloom");
+            game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][x as usize].pest =
+                Some(Pest { pest_type, infestation_level: 0.4 });
+        }
+
+        assert!(engine::apply_treatment(&mut game, (0, 0), 0, 0, Treatment::Ladybugs));
+        assert!(engine::apply_treatment(&mut game, (0, 0), 1, 0, Treatment::Ladybugs));
+        assert!(engine::apply_treatment(&mut game, (0, 0), 2, 0, Treatment::InsecticidalSoap));
+
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        assert!(tiles[0][0].pest.is_none(), "Ladybugs devour aphids.");
+        assert!(tiles[0][1].pest.as_ref().unwrap().infestation_level > 0.2, "Ladybugs barely touch whiteflies.");
+        let soaped = tiles[0][2].pest.as_ref().unwrap().infestation_level;
+        assert!(soaped > 0.0 && soaped < 0.4, "Soap only knocks aphids back.");
+        assert!(!game.treatment_resistance.contains_key(&Treatment::Ladybugs), "Predators never breed resistance.");
+    }
+
+    #[test]
+    fn test_treatment_overuse_builds_resistance() {
+        use crate::pests::{Pest, PestType};
+        use crate::treatment::Treatment;
+
+        let mut game = setup_test_game();
+        game.current_weather = crate::weather::Weather::Sunny;
+        game.wallet = Money::from_cents(100_000);
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        let tile = crate::targeting::TileSelection {
+            xs: crate::targeting::AxisRange::parse("0").unwrap(),
+            ys: crate::targeting::AxisRange::parse("0").unwrap(),
+            filters: Vec::new(),
+        };
+        let knocked_back = |game: &mut MainGameState| {
+            game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].pest =
+                Some(Pest { pest_type: PestType::Aphids, infestation_level: 1.0 });
+            engine::treat_selection(game, (0, 0), &tile, Treatment::NeemOil);
+            1.0 - game.plots[&(0, 0)].grid.tiles[0][0].pest.as_ref().map_or(0.0, |pest| pest.infestation_level)
+        };
+        let fresh = knocked_back(&mut game);
+        for _ in 0..20 {
+            knocked_back(&mut game);
+        }
+        assert!(knocked_back(&mut game) < fresh, "Overused treatments lose their punch.");
+        assert!(game.treatment_resistance[&Treatment::NeemOil] > 0.0);
+        assert!(game.plots[&(0, 0)].grid.tiles[0][0].soil.residue > 0.0, "Neem leaves residue in the soil.");
+    }
+
+    #[test]
+    fn test_treating_a_clean_plot_costs_nothing_and_builds_resistance_once() {
+        use crate::pests::{Pest, PestType};
+        use crate::treatment::{Treatment, RESISTANCE_BUILDUP};
+
+        let mut game = setup_test_game();
+        for x in 0..10 {
+            for y in 0..10 {
+                plant_seed(&mut game, (0, 0), x, y, "Crimson Bloom");
+            }
+        }
+        let all = crate::targeting::TileSelection {
+            xs: crate::targeting::AxisRange::ALL,
+            ys: crate::targeting::AxisRange::ALL,
+            filters: Vec::new(),
+        };
+        let wallet = game.wallet;
+        let summary = engine::treat_selection(&mut game, (0, 0), &all, Treatment::NeemOil);
+        assert_eq!(summary.applied, 0);
+        assert_eq!(game.wallet, wallet);
+        assert!(!game.treatment_resistance.contains_key(&Treatment::NeemOil));
+
+        for x in 0..3 {
+            game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][x].pest =
+                Some(Pest { pest_type: PestType::Aphids, infestation_level: 1.0 });
+        }
+        let summary = engine::treat_selection(&mut game, (0, 0), &all, Treatment::NeemOil);
+        assert_eq!(summary.applied, 3);
+        assert_eq!(game.treatment_resistance[&Treatment::NeemOil], RESISTANCE_BUILDUP);
+
+        // Biological controls are only released where there is something for them to eat
+        let wallet = game.wallet;
+        assert!(!engine::apply_treatment(&mut game, (0, 0), 5, 5, Treatment::Ladybugs));
+        assert!(!engine::apply_treatment(&mut game, (0, 0), 5, 5, Treatment::PredatoryMites));
+        assert_eq!(game.wallet, wallet);
+        assert_eq!(game.plots[&(0, 0)].grid.tiles[5][5].beneficials.ladybugs, 0.0);
+        assert!(engine::apply_treatment(&mut game, (0, 0), 0, 0, Treatment::Ladybugs), "Ladybugs go after aphids.");
+    }
+
+    #[test]
+    fn test_old_pesticide_scripts_still_parse() {
+        use crate::cli::{Args, Commands};
+        use clap::Parser;
+
+        let args = Args::try_parse_from(["bytebloom_gardens", "pesticide", "3", "4"]).unwrap();
+        assert!(matches!(args.command, Commands::Pesticide { .. }));
+        assert!(args.command.mutates_state());
+    }
+
+    #[test]
+    fn test_treatments_use_inventory_before_cash() {
+        use crate::treatment::Treatment;
+
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        let blight = crate::disease::Disease::new(crate::disease::DiseaseType::Blight);
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].disease = Some(blight.clone());
+        game.inventory.insert(Treatment::Fungicide.item_name().to_string(), 1);
+        let wallet = game.wallet;
+
+        assert!(engine::apply_treatment(&mut game, (0, 0), 0, 0, Treatment::Fungicide));
+        assert_eq!(game.wallet, wallet);
+        assert_eq!(game.inventory[Treatment::Fungicide.item_name()], 0);
+
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].disease = Some(blight);
+        assert!(engine::apply_treatment(&mut game, (0, 0), 0, 0, Treatment::Fungicide));
+        assert_eq!(game.wallet, wallet - Money::from_f32(Treatment::Fungicide.profile().cost));
+    }
+
+    #[test]
+    fn test_bees_pollinate_flowering_plants_and_raise_yield() {
+        let mut game = setup_test_game();
+        game.current_weather = crate::weather::Weather::Cloudy;
+        for x in [0, 5] {
+            plant_seed(&mut game, (0, 0), x, 0, "Crimson Bloom");
+            let plant = game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][x as usize].plant.as_mut().unwrap();
+            plant.genetics.yield_range = (10, 10);
+            plant.genetics.pest_resistance = 1.0;
+        }
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].plant.as_mut().unwrap().life_cycle_stage =
+            LifeCycleStage::Mature;
+
+        for _ in 0..10 {
+            engine::process_pests(&mut game);
+        }
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        assert!(tiles[0][0].beneficials.bees > 0.5, "Bees should gather on flowers.");
+        assert_eq!(tiles[0][5].beneficials.bees, 0.0);
+
+        for x in [0, 5] {
+            game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][x].plant.as_mut().unwrap().life_cycle_stage =
+                LifeCycleStage::Fruiting;
+        }
+        assert!(engine::harvest(&mut game, (0, 0), 0, 0));
+        let pollinated = game.inventory["Crimson Bloom"];
+        assert!(engine::harvest(&mut game, (0, 0), 5, 0));
+        assert!(pollinated > game.inventory["Crimson Bloom"] - pollinated, "Pollination should raise yield.");
+    }
+
+    #[test]
+    fn test_ladybugs_hunt_aphids_and_sprays_kill_beneficials() {
+        use crate::pests::{Pest, PestType};
+        use crate::treatment::Treatment;
+
+        let mut game = setup_test_game();
+        game.current_weather = crate::weather::Weather::Cloudy;
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        {
+            let tile = &mut game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0];
+            tile.plant.as_mut().unwrap().genetics.pest_resistance = 1.0;
+            tile.pest = Some(Pest { pest_type: PestType::Aphids, infestation_level: 0.5 });
+        }
+
+        let mut ticks = 0;
+        while game.plots[&(0, 0)].grid.tiles[0][0].pest.is_some() && ticks < 30 {
+            engine::process_pests(&mut game);
+            ticks += 1;
+        }
+        let tile = &game.plots[&(0, 0)].grid.tiles[0][0];
+        assert!(tile.pest.is_none(), "Ladybugs drawn in by aphids should eat them.");
+        let ladybugs = tile.beneficials.ladybugs;
+        assert!(ladybugs > 0.0);
+
+        game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].pest =
+            Some(Pest { pest_type: PestType::Whiteflies, infestation_level: 0.5 });
+        assert!(engine::apply_treatment(&mut game, (0, 0), 0, 0, Treatment::NeemOil));
+        assert!(game.plots[&(0, 0)].grid.tiles[0][0].beneficials.ladybugs < ladybugs, "Sprays kill beneficials too.");
+    }
+
+    #[test]
+    fn test_gene_pairs_express_dominant_over_recessive() {
+        use crate::genome::{Allele, Dominance, GenePair};
+
+        let dominant = Allele { value: 5.0, dominance: Dominance::Dominant };
+        let recessive = Allele { value: 10.0, dominance: Dominance::Recessive };
+        assert_eq!(GenePair(dominant, recessive).express(), 5.0);
+        assert_eq!(GenePair(recessive, dominant).express(), 5.0);
+        assert_eq!(GenePair(recessive, recessive).express(), 10.0);
+        assert_eq!(GenePair(dominant, Allele { value: 7.0, ..dominant }).express(), 6.0);
+    }
+
+    #[test]
+    fn test_planted_individuals_vary_around_their_species() {
+        use crate::genome::{Genome, ALLELE_VARIATION};
+
+        let template = &crate::catalogue::catalogue().iter().next().unwrap().template;
+        let uniform = Genome::uniform(&template.genetics).express(&template.genetics);
+        assert_eq!(uniform.light_req, template.genetics.light_req);
+        assert_eq!(uniform.yield_range, template.genetics.yield_range);
+
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, &template.species);
+        plant_seed(&mut game, (0, 0), 1, 0, &template.species);
+        let tiles = &game.plots[&(0, 0)].grid.tiles;
+        let (first, second) = (tiles[0][0].plant.as_ref().unwrap(), tiles[0][1].plant.as_ref().unwrap());
+        assert_ne!(first.genome, second.genome, "Each planted individual should get its own genome.");
+        for plant in [first, second] {
+            let drift = (plant.genetics.light_req - template.genetics.light_req).abs();
+            assert!(drift <= template.genetics.light_req * ALLELE_VARIATION + 1e-4);
+            assert_eq!(plant.genome.express(&template.genetics).light_req, plant.genetics.light_req);
+        }
+    }
+
+    #[test]
+    fn test_collected_seeds_carry_the_parent_genome() {
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        {
+            let plant = game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].plant.as_mut().unwrap();
+            plant.life_cycle_stage = LifeCycleStage::Fruiting;
+            plant.genetics.yield_range = (10, 10);
+            plant.genetics.genetic_stability = 1.0;
+        }
+        let parent_genome = game.plots[&(0, 0)].grid.tiles[0][0].plant.as_ref().unwrap().genome.clone();
+
+        assert!(engine::collect_seeds(&mut game, (0, 0), 0, 0));
+        assert_eq!(game.inventory["Crimson Bloom"], 5, "Half the yield goes to seed.");
+        let (&lot_id, lot) = game.seed_lots.iter().next().unwrap();
+        assert_eq!(lot.quantity, 5);
+        assert_eq!((&lot.mother, &lot.father), (&parent_genome, &parent_genome));
+
+        assert!(engine::plant_seed_lot(&mut game, (0, 0), 1, 0, lot_id));
+        let seedling = game.plots[&(0, 0)].grid.tiles[0][1].plant.as_ref().unwrap();
+        for (gene, pair) in &seedling.genome.genes {
+            let parent = parent_genome.genes[gene];
+            for allele in [pair.0, pair.1] {
+                assert!(allele == parent.0 || allele == parent.1, "A stable plant's seeds only carry its own alleles.");
+            }
+        }
+        assert_eq!(game.seed_lots[&lot_id].quantity, 4);
+        assert!(!engine::plant_seed_lot(&mut game, (0, 0), 2, 0, lot_id + 1));
+    }
+
+    #[test]
+    fn test_seeds_in_a_lot_are_individuals() {
+        let mut game = setup_test_game();
+        flowering(&mut game, 0, 0, "Crimson Bloom");
+        flowering(&mut game, 1, 0, "Crimson Bloom");
+        let lot_id = engine::breed(&mut game, (0, 0), (0, 0), (1, 0)).unwrap();
+
+        for x in 0..3 {
+            assert!(engine::plant_seed_lot(&mut game, (0, 0), x, 5, lot_id));
+        }
+        let row = &game.plots[&(0, 0)].grid.tiles[5];
+        let genomes: Vec<_> = row[..3].iter().map(|tile| &tile.plant.as_ref().unwrap().genome).collect();
+        assert!(genomes[0] != genomes[1] || genomes[1] != genomes[2], "Each seed should segregate on its own.");
+    }
+
+    #[test]
+    fn test_a_harvest_with_no_yield_sets_no_seed() {
+        let mut game = setup_test_game();
+        plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom");
+        {
+            let plant = game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][0].plant.as_mut().unwrap();
+            plant.life_cycle_stage = LifeCycleStage::Fruiting;
+            plant.genetics.yield_range = (0, 0);
+        }
+
+        assert!(engine::collect_seeds(&mut game, (0, 0), 0, 0));
+        assert!(game.seed_lots.is_empty());
+        assert_eq!(game.inventory["Crimson Bloom"], 0);
+    }
+
+    #[test]
+    fn test_mutation_rate_follows_genetic_stability() {
+        use crate::genome::{Gene, Genome};
+
+        let template = &crate::catalogue::catalogue().iter().next().unwrap().template;
+        let mut rng = crate::rng::seeded(7);
+        let mut genome = Genome::individual(&template.genetics, &mut rng);
+        let original = genome.clone();
+
+        assert_eq!(genome.mutate(1.0, &mut rng), 0);
+        assert_eq!(genome, original);
+        let loci: usize = Gene::ALL.iter().map(Gene::loci).sum();
+        assert_eq!(genome.mutate(0.0, &mut rng), 2 * loci as u32);
+        assert_ne!(genome, original);
+    }
+
+    #[test]
+    fn test_yield_and_resistances_are_polygenic() {
+        use crate::genome::{Gene, GenePair, Genome, POLYGENIC_LOCI};
+
+        let template = &crate::catalogue::catalogue().get("Crimson Bloom").unwrap().template;
+        let mut genome = Genome::uniform(&template.genetics);
+        assert_eq!(genome.minor_genes[&Gene::PestResistance].len(), POLYGENIC_LOCI - 1);
+        assert!(!genome.minor_genes.contains_key(&Gene::Height), "Height rests on one gene pair.");
+
+        // One gene pair out of four moves a polygenic trait only a quarter of the way
+        genome.genes.insert(Gene::PestResistance, GenePair::homozygous(1.0));
+        genome.genes.insert(Gene::Height, GenePair::homozygous(5.0));
+        let genetics = genome.express(&template.genetics);
+        let expected = (1.0 + 3.0 * template.genetics.pest_resistance) / 4.0;
+        assert!((genetics.pest_resistance - expected).abs() < 1e-6);
+        assert_eq!(genetics.height, 5.0);
+
+        // Genomes saved before traits were polygenic express their single pair
+        genome.minor_genes.clear();
+        assert_eq!(genome.express(&template.genetics).pest_resistance, 1.0);
+        let json = serde_json::json!({ "genes": serde_json::to_value(&genome.genes).unwrap() });
+        assert_eq!(serde_json::from_value::<Genome>(json).unwrap(), genome);
+    }
+
+    fn flowering(game: &mut MainGameState, x: u32, y: u32, species: &str) {
+        plant_seed(game, (0, 0), x, y, species);
+        let plant = game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[y as usize][x as usize].plant.as_mut().unwrap();
+        plant.life_cycle_stage = LifeCycleStage::Mature;
+    }
+
+    #[test]
+    fn test_breeding_two_species_creates_a_plantable_hybrid() {
+        let mut game = setup_test_game();
+        flowering(&mut game, 0, 0, "Crimson Bloom");
+        flowering(&mut game, 2, 1, "Azure Fern");
+
+        let lot_id = engine::breed(&mut game, (0, 0), (0, 0), (2, 1)).unwrap();
+        let lot = &game.seed_lots[&lot_id];
+        assert_eq!(lot.species, "Crimson Fern");
+        assert_eq!(lot.quantity, crate::breeding::SEEDS_PER_CROSS);
+        let hybrid = &game.hybrids["Crimson Fern"];
+        assert_eq!(hybrid.parents, ("Crimson Bloom".to_string(), "Azure Fern".to_string()));
+        assert!(game.market.listings.contains_key("Crimson Fern"), "A new hybrid should be listed on the market.");
+
+        // Crossing the pair again, either way round, breeds the same hybrid.
+        let again = engine::breed(&mut game, (0, 0), (2, 1), (0, 0)).unwrap();
+        assert_eq!(game.seed_lots[&again].species, "Crimson Fern");
+        assert_eq!(game.hybrids.len(), 1);
+
+        assert!(engine::plant_seed_lot(&mut game, (0, 0), 5, 5, lot_id));
+        let seed_item = crate::economy::seed_item("Crimson Fern");
+        engine::buy(&mut game, &seed_item, 1).unwrap();
+        assert!(plant_seed(&mut game, (0, 0), 6, 5, "Crimson Fern"));
+        assert_eq!(game.plots[&(0, 0)].grid.tiles[5][6].plant.as_ref().unwrap().species, "Crimson Fern");
+    }
+
+    #[test]
+    fn test_breeding_needs_flowering_parents_in_range() {
+        let mut game = setup_test_game();
+        flowering(&mut game, 0, 0, "Crimson Bloom");
+        plant_seed(&mut game, (0, 0), 1, 0, "Azure Fern");
+        flowering(&mut game, 9, 9, "Golden Pine");
+
+        assert!(engine::breed(&mut game, (0, 0), (0, 0), (1, 0)).is_err(), "A seedling cannot be crossed.");
+        assert!(engine::breed(&mut game, (0, 0), (0, 0), (9, 9)).is_err(), "Plants too far apart cannot cross.");
+        assert!(engine::breed(&mut game, (0, 0), (0, 0), (0, 0)).is_err());
+        assert!(engine::breed(&mut game, (0, 0), (0, 0), (2, 2)).is_err(), "There is no plant at (2, 2).");
+        assert!(game.seed_lots.is_empty() && game.hybrids.is_empty());
+    }
+
+    #[test]
+    fn test_lineage_traces_seed_lots_back_to_bought_seed() {
+        let mut game = setup_test_game();
+        flowering(&mut game, 0, 0, "Crimson Bloom");
+        flowering(&mut game, 1, 0, "Azure Fern");
+        let bred = engine::breed(&mut game, (0, 0), (0, 0), (1, 0)).unwrap();
+        assert!(engine::plant_seed_lot(&mut game, (0, 0), 2, 0, bred));
+        let grandchild = game.plots.get_mut(&(0, 0)).unwrap().grid.tiles[0][2].plant.as_mut().unwrap();
+        grandchild.life_cycle_stage = LifeCycleStage::Fruiting;
+        let grandchild_id = grandchild.id;
+        assert_eq!(grandchild.parents, vec![bred]);
+        assert_eq!(grandchild.generation, 1);
+
+        assert!(engine::collect_seeds(&mut game, (0, 0), 2, 0));
+        let (&collected, lot) = game.seed_lots.iter().next_back().unwrap();
+        assert_eq!(lot.parents, vec![grandchild_id]);
+        assert_eq!(lot.generation, 2);
+
+        let tree = crate::lineage::family_tree(&game.pedigree, collected).unwrap();
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(lines.len(), 5, "The lot, its plant, the bred lot and both grandparents:\n{}", tree);
+        assert!(lines[0].starts_with(&format!("#{} seed lot Crimson Fern, gen 2", collected)));
+        assert!(lines[3].contains("Crimson Bloom, gen 0") && lines[4].contains("Azure Fern, gen 0"));
+        assert!(crate::lineage::family_tree(&game.pedigree, 999).is_none());
+    }
+
+    #[test]
+    fn test_unknown_species_is_an_error() {
+        let mut game = setup_test_game();
+        assert!(!plant_seed(&mut game, (0, 0), 0, 0, "tomato"), "A mistyped species must not plant something random.");
+        assert!(game.plots[&(0, 0)].grid.tiles[0][0].plant.is_none());
+        assert!(crate::plant::create_plant("tomato", &game.hybrids, &mut game.rng).is_err());
+    }
+
+    #[test]
+    fn test_catalogue_definitions_are_validated() {
+        use crate::catalogue::{Catalogue, Category, SpeciesDefinition};
+
+        let json = r#"[{"name": "Tomato", "category": "vegetable", "rarity": "uncommon",
+            "maturity_age": 10, "wither_time": 15, "growth_time": 8, "yield_range": [3, 8],
+            "ideal_moisture_range": [0.4, 0.6], "nutrient_consumption": [0.1, 0.1, 0.1],
+            "light_req": 5.0, "pest_resistance": 0.1, "disease_resistance": 0.1,
+            "genetic_stability": 0.9}]"#;
+        let mut definitions: Vec<SpeciesDefinition> = serde_json::from_str(json).unwrap();
+        let catalogue = Catalogue::from_definitions(&definitions).ok().unwrap();
+        let tomato = catalogue.get("Tomato").unwrap();
+        assert_eq!(tomato.category, Category::Vegetable);
+        assert_eq!(tomato.template.genetics.yield_range, (3, 8));
+
+        definitions.push(definitions[0].clone());
+        assert!(Catalogue::from_definitions(&definitions).err().unwrap().contains("twice"));
+        definitions.pop();
+        definitions[0].genetics.yield_range = (8, 3);
+        assert!(Catalogue::from_definitions(&definitions).err().unwrap().contains("yield_range"));
+    }
+
+    #[test]
+    fn test_catalogues_can_be_written_in_toml() {
+        use crate::catalogue::{read_definitions, Catalogue, Category};
+
+        let toml = r#"
+            [[species]]
+            name = "Tomato"
+            category = "vegetable"
+            maturity_age = 10
+            wither_time = 15
+            growth_time = 8
+            yield_range = [3, 8]
+            ideal_moisture_range = [0.4, 0.6]
+            nutrient_consumption = [0.1, 0.1, 0.1]
+            light_req = 5
+            pest_resistance = 0.1
+            disease_resistance = 0.1
+            genetic_stability = 0.9
+        "#;
+        let filename = "test_catalogue.toml";
+        std::fs::write(filename, toml).unwrap();
+        let definitions = read_definitions(filename);
+        std::fs::remove_file(filename).unwrap();
+
+        let catalogue = Catalogue::from_definitions(&definitions.unwrap()).ok().unwrap();
+        let tomato = catalogue.get("Tomato").unwrap();
+        assert_eq!(tomato.category, Category::Vegetable);
+        assert_eq!(tomato.template.genetics.yield_range, (3, 8));
+        assert_eq!(tomato.template.genetics.light_req, 5.0);
+    }
+
+    #[test]
+    fn test_built_in_species_fit_their_category() {
+        use crate::catalogue::{catalogue, Category};
+
+        let of = |category: Category| catalogue().iter().filter(move |species| species.category == category);
+        let shortest_tree = of(Category::Tree).map(|tree| tree.template.genetics.height).fold(f32::INFINITY, f32::min);
+        let tallest_other = of(Category::Flower)
+            .chain(of(Category::Vegetable))
+            .map(|species| species.template.genetics.height)
+            .fold(0.0, f32::max);
+        assert!(shortest_tree > 2.0 * tallest_other, "Trees should tower over flowers and vegetables.");
+        assert!(of(Category::Tree).all(|tree| tree.template.wither_time >= tree.template.maturity_age + 15), "Trees fruit for long.");
+        assert!(of(Category::Vegetable).all(|vegetable| vegetable.template.genetics.growth_time < 10));
+    }
+
+    #[test]
+    fn test_market_trades_the_catalogue() {
+        use crate::catalogue::{catalogue, Rarity};
+        use crate::economy::{self, ItemKind};
+
+        let mut game = setup_test_game();
+        for species in catalogue().iter() {
+            let name = &species.template.species;
+            assert_eq!(game.market.listings[name].kind, ItemKind::Produce);
+            assert_eq!(game.market.listings[&economy::seed_item(name)].kind, ItemKind::Seed);
+        }
+        assert_eq!(game.market.listings["Neem Oil"].kind, ItemKind::Supply);
+
+        let genetics = &catalogue().get("Crimson Bloom").unwrap().template.genetics;
+        assert!(economy::produce_price(genetics, Rarity::Rare) > economy::produce_price(genetics, Rarity::Common));
+
+        game.inventory.insert("Crimson Bloom".to_string(), 3);
+        let wallet = game.wallet;
+        engine::sell(&mut game, "Crimson Bloom", 3).unwrap();
+        assert!(game.wallet > wallet, "Harvested produce should now be sellable.");
+        assert!(engine::buy(&mut game, "Crimson Bloom", 1).is_err());
+        assert!(engine::sell(&mut game, "Neem Oil", 1).is_err());
+    }
+
+    #[test]
+    fn test_planting_uses_up_bought_seed() {
+        use crate::economy::{quote_purchase, seed_item};
+
+        let mut game = setup_test_game();
+        game.seed_inventory.clear();
+        assert!(!plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom"), "Planting needs seed in stock.");
+
+        let quote = quote_purchase(&game.market, &seed_item("Crimson Bloom"), 2).unwrap();
+        let wallet = game.wallet;
+        let cost = engine::buy(&mut game, &seed_item("Crimson Bloom"), 2).unwrap();
+        assert_eq!(cost, quote + quote.scale(game.fees.transaction_rate));
+        assert_eq!(game.wallet, wallet - cost);
+        assert_eq!(game.seed_inventory["Crimson Bloom"], 2);
+        assert!(game.inventory.is_empty(), "Seed is kept apart from produce and supplies.");
+
+        assert!(plant_seed(&mut game, (0, 0), 0, 0, "Crimson Bloom"));
+        assert!(plant_seed(&mut game, (0, 0), 1, 0, "Crimson Bloom"));
+        assert!(!plant_seed(&mut game, (0, 0), 2, 0, "Crimson Bloom"));
+        assert_eq!(game.seed_inventory["Crimson Bloom"], 0);
+    }
+
+    #[test]
+    fn test_supplies_go_to_the_inventory() {
+        let mut game = setup_test_game();
+        engine::buy(&mut game, "Neem Oil", 2).unwrap();
+        assert_eq!(game.inventory["Neem Oil"], 2);
+        assert!(!game.seed_inventory.contains_key("Neem Oil"));
+    }
+
+    #[test]
+    fn test_large_sales_slip_and_prices_recover() {
+        use crate::economy::{sell_item, update_market_prices};
+
+        let mut unit_by_unit = setup_test_game();
+        unit_by_unit.inventory.insert("Crimson Bloom".to_string(), 40);
+        let one_at_a_time: Money = (0..40)
+            .map(|_| {
+                let game = &mut unit_by_unit;
+                sell_item(&mut game.inventory, &mut game.market, "Crimson Bloom", 1).unwrap()
+            })
+            .sum();
+
+        let mut game = setup_test_game();
+        let base = game.market.price("Crimson Bloom").unwrap();
+
+        game.inventory.insert("Crimson Bloom".to_string(), 40);
+        let proceeds = sell_item(&mut game.inventory, &mut game.market, "Crimson Bloom", 40).unwrap();
+        assert!(proceeds < Money::from_f32(40.0 * base), "Each unit of a big sale should fetch less than the last.");
+        assert_eq!(proceeds, one_at_a_time, "Selling in one go or unit by unit should pay the same.");
+        let crashed = game.market.price("Crimson Bloom").unwrap();
+        assert!(crashed < base * 0.7);
+
+        let mut rng = crate::rng::seeded(42);
+        for _ in 0..100 {
+            update_market_prices(&mut game.market, &mut rng);
+        }
+        let recovered = game.market.price("Crimson Bloom").unwrap();
+        assert!(recovered > crashed && (recovered - base).abs() < base * 0.2, "Prices should drift back to base.");
+    }
+
+    #[test]
+    fn test_huge_trades_are_priced_at_once() {
+        use crate::economy::{quote_purchase, sell_item, PRICE_CEILING_SHARE, PRICE_FLOOR_SHARE};
+
+        let mut game = setup_test_game();
+        let wallet = game.wallet;
+        let ceiling = game.market.listings["Neem Oil"].base_price * PRICE_CEILING_SHARE;
+        let quote = quote_purchase(&game.market, "Neem Oil", 4_000_000_000).unwrap();
+        assert!(quote > Money::from_f32(ceiling).times(3_000_000_000), "Nearly every unit goes at the ceiling.");
+        assert!(engine::buy(&mut game, "Neem Oil", 4_000_000_000).is_err());
+        assert_eq!(game.wallet, wallet);
+
+        game.inventory.insert("Crimson Bloom".to_string(), 4_000_000_000);
+        let floor = game.market.listings["Crimson Bloom"].base_price * PRICE_FLOOR_SHARE;
+        let proceeds = sell_item(&mut game.inventory, &mut game.market, "Crimson Bloom", 4_000_000_000).unwrap();
+        assert!(proceeds < Money::from_f32(floor).times(4_100_000_000));
+        assert_eq!(game.market.price("Crimson Bloom").unwrap(), floor);
+    }
+
+    #[test]
+    fn test_market_prices_do_not_compound() {
+        use crate::economy::{update_market_prices, Market, PRICE_CEILING_SHARE, PRICE_FLOOR_SHARE};
+
+        let run = |seed: u64| {
+            let mut market = Market::default();
+            let mut rng = crate::rng::seeded(seed);
+            for _ in 0..2000 {
+                update_market_prices(&mut market, &mut rng);
+            }
+            market
+        };
+        let market = run(7);
+        for listing in market.listings.values() {
+            assert!(listing.price >= listing.base_price * PRICE_FLOOR_SHARE);
+            assert!(listing.price <= listing.base_price * PRICE_CEILING_SHARE);
+            assert!((listing.price / listing.base_price - 1.0).abs() < 0.5, "A quiet market should stay near base.");
+        }
+        let replay = run(7);
+        assert!(market.listings.iter().all(|(item, listing)| replay.listings[item].price == listing.price));
+    }
+
+    #[test]
+    fn test_price_history_is_kept_per_tick() {
+        use crate::economy::{recent_prices, update_market_prices, PRICE_HISTORY_LENGTH};
+
+        let mut game = setup_test_game();
+        assert_eq!(recent_prices(&game.market, "Crimson Bloom", 10), Some(Vec::new()));
+        for _ in 0..PRICE_HISTORY_LENGTH + 5 {
+            update_market_prices(&mut game.market, &mut game.rng);
+        }
+        let history = recent_prices(&game.market, "Crimson Bloom", usize::MAX).unwrap();
+        assert_eq!(history.len(), PRICE_HISTORY_LENGTH, "Only the most recent ticks are kept.");
+        assert_eq!(history.last().copied(), game.market.price("Crimson Bloom"));
+        assert_eq!(recent_prices(&game.market, "Crimson Bloom", 10).unwrap(), history[history.len() - 10..]);
+        assert!(recent_prices(&game.market, "tomato", 10).is_none());
+    }
+
+    #[test]
+    fn test_price_history_report_charts_and_summarises() {
+        use crate::economy::{price_history_report, sparkline};
+
+        assert_eq!(sparkline(&[1.0, 2.0, 3.0]), "▁▅█");
+        assert_eq!(sparkline(&[2.0, 2.0]), "▅▅");
+
+        let mut game = setup_test_game();
+        assert!(price_history_report(&game.market, "Crimson Bloom", 30).is_err(), "No ticks have passed yet.");
+        let listing = game.market.listings.get_mut("Crimson Bloom").unwrap();
+        listing.history = [4.0, 1.0, 2.0, 5.0].into_iter().collect();
+        let report = price_history_report(&game.market, "Crimson Bloom", 3).unwrap();
+        assert!(report.contains("last 3 tick(s)"));
+        assert!(report.contains("▁▃█"));
+        assert!(report.contains("now 5.00 | min 1.00 | max 5.00 | mean 2.67"), "{}", report);
+    }
+
+    #[test]
+    fn test_market_panel_summarises_recent_prices() {
+        use crate::tui::market_panel;
+
+        let mut game = setup_test_game();
+        game.inventory.insert("Crimson Bloom".to_string(), 2);
+        assert!(!market_panel(&game).contains("mean"), "There is nothing to summarise before the first tick.");
+        let listing = game.market.listings.get_mut("Crimson Bloom").unwrap();
+        listing.history = [4.0, 1.0, 2.0, 5.0].into_iter().collect();
+        let panel = market_panel(&game);
+        assert!(panel.contains("min 1.00 | max 5.00 | mean 3.00"), "{}", panel);
+    }
+
+    #[test]
+    fn test_limit_sell_order_fills_as_the_price_allows() {
+        use crate::orders::{OrderStatus, Side, Trigger};
+
+        let mut game = setup_test_game();
+        let base = game.market.price("Crimson Bloom").unwrap();
+        game.inventory.insert("Crimson Bloom".to_string(), 100);
+        let id = engine::place_order(&mut game, Side::Sell, "Crimson Bloom", 100, Trigger::AtOrAbove(base * 0.9), None).unwrap();
+
+        let updates = engine::process_orders(&mut game);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].status, OrderStatus::PartiallyFilled, "Slippage should stop the sale at the limit.");
+        let sold = updates[0].quantity;
+        assert!(sold > 0 && sold < 100);
+        let sale = game.ledger.transactions.last().unwrap();
+        assert_eq!((sale.kind, sale.quantity, sale.net), (TransactionKind::Sale, sold, updates[0].value));
+        let least = Money::from_f32(sold as f32 * base * 0.9) - Money::from_cents(sold as i64);
+        assert!(sale.net + sale.fees >= least, "Every unit should sell at the limit or better.");
+        assert_eq!(game.orders[&id].remaining, 100 - sold);
+        assert_eq!(game.inventory["Crimson Bloom"], 100 - sold);
+        assert!(engine::process_orders(&mut game).is_empty(), "Nothing more fills until the price recovers.");
+
+        let summary = engine::advance(&mut game, 60, Some(crate::weather::Weather::Sunny));
+        let refilled = summary.ticks.iter().flat_map(|tick| &tick.order_updates).any(|update| update.order_id == id);
+        assert!(refilled, "The order should fill further as the price recovers.");
+        assert!(engine::place_order(&mut game, Side::Buy, "Crimson Bloom", 1, Trigger::AtOrBelow(1.0), None).is_err());
+    }
+
+    #[test]
+    fn test_orders_need_a_positive_trigger_price() {
+        use crate::orders::{Side, Trigger};
+
+        let mut game = setup_test_game();
+        game.inventory.insert("Crimson Bloom".to_string(), 10);
+        for price in [f32::NAN, f32::INFINITY, -1.0, 0.0] {
+            let trigger = Trigger::AtOrAbove(price);
+            assert!(engine::place_order(&mut game, Side::Sell, "Crimson Bloom", 1, trigger, None).is_err(), "{}", price);
+        }
+        assert!(game.orders.is_empty());
+        assert!(engine::place_order(&mut game, Side::Sell, "Crimson Bloom", 1, Trigger::AtOrAbove(0.5), None).is_ok());
+    }
+
+    #[test]
+    fn test_order_expiry_cannot_overflow() {
+        use crate::orders::{Side, Trigger};
+
+        let mut game = setup_test_game();
+        engine::advance(&mut game, 1, Some(crate::weather::Weather::Sunny));
+        let trigger = Trigger::AtOrBelow(100.0);
+        assert!(engine::place_order(&mut game, Side::Buy, "Neem Oil", 1, trigger, Some(u64::MAX)).is_err());
+        assert!(game.orders.is_empty());
+        let id = engine::place_order(&mut game, Side::Buy, "Neem Oil", 1, trigger, Some(u64::MAX - 1)).unwrap();
+        assert_eq!(game.orders[&id].expires_at, Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_stop_orders_fill_fully_and_orders_expire() {
+        use crate::economy::seed_item;
+        use crate::orders::{OrderStatus, Side, Trigger};
+
+        let mut game = setup_test_game();
+        let base = game.market.price("Crimson Bloom").unwrap();
+        game.inventory.insert("Crimson Bloom".to_string(), 30);
+        engine::place_order(&mut game, Side::Sell, "Crimson Bloom", 30, Trigger::AtOrBelow(base * 1.5), None).unwrap();
+        let never = engine::place_order(&mut game, Side::Buy, &seed_item("Sunpetal"), 5, Trigger::AtOrBelow(0.001), Some(2)).unwrap();
+
+        let updates = engine::process_orders(&mut game);
+        assert_eq!(updates.len(), 1);
+        assert_eq!((updates[0].quantity, updates[0].status), (30, OrderStatus::Filled), "A stop sells everything once triggered.");
+        assert_eq!(game.inventory["Crimson Bloom"], 0);
+
+        let summary = engine::advance(&mut game, 3, Some(crate::weather::Weather::Sunny));
+        let expired: Vec<_> = summary.ticks.iter().flat_map(|tick| &tick.order_updates).collect();
+        assert_eq!(expired.len(), 1);
+        assert_eq!((expired[0].order_id, expired[0].status), (never, OrderStatus::Expired));
+        assert_eq!(summary.ticks[2].order_updates.len(), 1, "It may still fill on tick 2 and expires after it.");
+        assert!(game.orders.is_empty());
+        assert!(summary.report().contains(&format!("order #{} for Sunpetal Seeds expired", never)));
+    }
+
+    #[test]
+    fn test_money_adds_up_without_drift() {
+        let mut wallet = Money::ZERO;
+        for _ in 0..10_000 {
+            wallet += Money::from_f32(0.1);
+        }
+        assert_eq!(wallet, Money::from_cents(100_000));
+        assert_eq!(Money::from_f32(1.125).to_string(), "1.13");
+        assert_eq!((-Money::from_cents(5)).to_string(), "-0.05");
+        assert_eq!(Money::from_cents(1000).scale(0.02), Money::from_cents(20));
+    }
+
+    #[test]
+    fn test_trades_and_expenses_are_recorded_in_the_ledger() {
+        let mut game = setup_test_game();
+        game.fees.transaction_rate = 0.1;
+        game.inventory.insert("Crimson Bloom".to_string(), 5);
+        let wallet = game.wallet;
+
+        let proceeds = engine::sell(&mut game, "Crimson Bloom", 5).unwrap();
+        let cost = engine::buy(&mut game, &crate::economy::seed_item("Crimson Bloom"), 2).unwrap();
+        assert!(engine::water_tile(&mut game, (0, 0), 0, 0));
+        assert!(engine::water_tile(&mut game, (0, 0), 1, 0));
+
+        let transactions = &game.ledger.transactions;
+        assert_eq!(transactions.len(), 3, "Repeated watering on one tick makes a single entry.");
+        let sale = &transactions[0];
+        assert_eq!((sale.kind, sale.quantity, sale.net), (TransactionKind::Sale, 5, proceeds));
+        assert_eq!(sale.fees, (sale.net + sale.fees).scale(0.1));
+        assert_eq!((transactions[1].kind, transactions[1].net), (TransactionKind::Purchase, -cost));
+        assert_eq!((transactions[2].item.as_str(), transactions[2].quantity), ("Watering", 2));
+        assert_eq!(transactions[2].net, -engine::WATER_COST.times(2));
+
+        let total: Money = transactions.iter().map(|transaction| transaction.net).sum();
+        assert_eq!(game.wallet, wallet + total, "The ledger should account for every cent.");
+    }
+
+    #[test]
+    fn test_profit_and_loss_by_crop_and_period() {
+        let mut game = setup_test_game();
+        game.inventory.insert("Crimson Bloom".to_string(), 4);
+        engine::buy(&mut game, &crate::economy::seed_item("Crimson Bloom"), 1).unwrap();
+        engine::sell(&mut game, "Crimson Bloom", 2).unwrap();
+        game.tick_counter = 12;
+        engine::sell(&mut game, "Crimson Bloom", 2).unwrap();
+        assert!(engine::water_tile(&mut game, (0, 0), 0, 0));
+
+        let crops = game.ledger.profit_by_crop();
+        assert_eq!(crops.len(), 1, "Watering is not tied to a crop.");
+        let crop = crops["Crimson Bloom"];
+        let sales: Money = game.ledger.transactions[1..3].iter().map(|transaction| transaction.net).sum();
+        assert_eq!((crop.revenue, crop.costs), (sales, -game.ledger.transactions[0].net));
+
+        let periods = game.ledger.profit_by_period(10);
+        assert_eq!(periods.keys().copied().collect::<Vec<_>>(), vec![0, 10]);
+        assert_eq!(periods[&10].costs, engine::WATER_COST);
+        let report = crate::ledger::ledger_report(&game.ledger, 10, 20);
+        assert!(report.contains("Crimson Bloom") && report.contains("10-19"));
+    }
+
+    #[test]
+    fn test_land_tax_is_charged_each_period() {
+        let mut game = setup_test_game();
+        game.fees.tax_period = 5;
+        game.fees.land_tax_per_tile = Money::from_cents(3);
+        let wallet = game.wallet;
+        let tiles = 10 * 10;
+
+        let taxed: Vec<Money> = (0..10).map(|_| run_game_tick(&mut game, None).land_tax).collect();
+        assert_eq!(taxed.iter().filter(|tax| **tax != Money::ZERO).count(), 2);
+        assert_eq!(taxed[4], Money::from_cents(3 * tiles));
+        assert_eq!(game.wallet, wallet - Money::from_cents(2 * 3 * tiles));
+        assert!(game.ledger.transactions.iter().all(|transaction| transaction.kind == TransactionKind::Tax));
+
+        assert!(game.fees.update(Some(1.5), None, None).is_err());
+        for absurd in [1e30, f32::NAN, f32::INFINITY, -0.01] {
+            assert!(game.fees.update(None, Some(absurd), None).is_err(), "{}", absurd);
+        }
+        assert_eq!(game.fees.land_tax_per_tile, Money::from_cents(3));
+        game.fees.update(None, Some(0.05), None).unwrap();
+        assert_eq!(game.fees.land_tax_per_tile, Money::from_cents(5));
+        assert_eq!(Money::from_cents(i64::MAX / 2).times(3), Money::from_cents(i64::MAX), "Products saturate.");
+        game.fees.update(None, None, Some(0)).unwrap();
+        assert_eq!(run_game_tick(&mut game, None).land_tax, Money::ZERO, "A tax period of 0 turns the tax off.");
+    }
 }
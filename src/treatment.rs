@@ -0,0 +1,126 @@
+// src/treatment.rs
+
+use crate::disease::DiseaseType;
+use crate::pests::PestType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Something the gardener can put on a tile to fight pests or disease.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum Treatment {
+    InsecticidalSoap,
+    NeemOil,
+    Ladybugs,
+    PredatoryMites,
+    Fungicide,
+}
+
+/// Cost and side effects of a treatment.
+pub struct TreatmentProfile {
+    /// Price per tile when none is held in the inventory.
+    pub cost: f32,
+    /// Chemical residue left in the soil per application.
+    pub residue: f32,
+    /// Whether pests and diseases grow resistant with repeated use.
+    pub builds_resistance: bool,
+    /// Plant health lost when applied during a heatwave.
+    pub heat_scorch: f32,
+    /// Share of the tile's beneficial insects killed by each application.
+    pub beneficial_harm: f32,
+}
+
+/// Treatment resistance gained each time a treatment is used, however many tiles it covers.
+pub const RESISTANCE_BUILDUP: f32 = 0.02;
+/// Treatment resistance lost per tick as unexposed generations take over.
+pub const RESISTANCE_DECAY: f32 = 0.002;
+/// Resistance never makes a treatment entirely useless.
+pub const MAX_RESISTANCE: f32 = 0.8;
+/// Infestation or disease severity below which a treatment clears it completely.
+pub const TREATMENT_CLEAR_LEVEL: f32 = 0.05;
+
+impl Treatment {
+    /// Soap is cheap and gentle but weak; neem is broad but leaves residue;
+    /// the predators are dear but precise and never bred against; fungicide
+    /// is strong against fungi and hard on the soil.
+    pub fn profile(&self) -> TreatmentProfile {
+        match self {
+            Treatment::InsecticidalSoap => TreatmentProfile {
+                cost: 0.3,
+                residue: 0.0,
+                builds_resistance: true,
+                heat_scorch: 0.1,
+                beneficial_harm: 0.3,
+            },
+            Treatment::NeemOil => TreatmentProfile {
+                cost: 0.6,
+                residue: 0.05,
+                builds_resistance: true,
+                heat_scorch: 0.05,
+                beneficial_harm: 0.5,
+            },
+            Treatment::Ladybugs => TreatmentProfile {
+                cost: 1.5,
+                residue: 0.0,
+                builds_resistance: false,
+                heat_scorch: 0.0,
+                beneficial_harm: 0.0,
+            },
+            Treatment::PredatoryMites => TreatmentProfile {
+                cost: 1.5,
+                residue: 0.0,
+                builds_resistance: false,
+                heat_scorch: 0.0,
+                beneficial_harm: 0.0,
+            },
+            Treatment::Fungicide => TreatmentProfile {
+                cost: 1.0,
+                residue: 0.15,
+                builds_resistance: true,
+                heat_scorch: 0.0,
+                beneficial_harm: 0.1,
+            },
+        }
+    }
+
+    /// Share of an infestation of `pest_type` one application removes.
+    pub fn efficacy_against_pest(&self, pest_type: &PestType) -> f32 {
+        match (self, pest_type) {
+            (Treatment::InsecticidalSoap, PestType::Aphids | PestType::Whiteflies) => 0.5,
+            (Treatment::InsecticidalSoap, PestType::SpiderMites) => 0.3,
+            (Treatment::NeemOil, _) => 0.6,
+            (Treatment::Ladybugs, PestType::Aphids) => 0.9,
+            (Treatment::Ladybugs, PestType::Whiteflies) => 0.3,
+            (Treatment::PredatoryMites, PestType::SpiderMites) => 0.9,
+            (Treatment::PredatoryMites, PestType::Whiteflies) => 0.2,
+            _ => 0.0,
+        }
+    }
+
+    /// Share of a `disease_type` infection's severity one application removes.
+    pub fn efficacy_against_disease(&self, disease_type: DiseaseType) -> f32 {
+        match (self, disease_type) {
+            (Treatment::NeemOil, DiseaseType::PowderyMildew) => 0.5,
+            (Treatment::Fungicide, DiseaseType::Blight | DiseaseType::PowderyMildew) => 0.9,
+            _ => 0.0,
+        }
+    }
+
+    /// The inventory item used up in place of paying `cost`.
+    pub fn item_name(&self) -> &'static str {
+        match self {
+            Treatment::InsecticidalSoap => "Insecticidal Soap",
+            Treatment::NeemOil => "Neem Oil",
+            Treatment::Ladybugs => "Ladybugs",
+            Treatment::PredatoryMites => "Predatory Mites",
+            Treatment::Fungicide => "Fungicide",
+        }
+    }
+}
+
+/// Lets resistance to every treatment fade a little; called once per tick.
+pub fn relax_resistance(resistance: &mut BTreeMap<Treatment, f32>) {
+    for level in resistance.values_mut() {
+        *level = (*level - RESISTANCE_DECAY).max(0.0);
+    }
+    resistance.retain(|_, level| *level > 0.0);
+}
@@ -13,7 +13,8 @@ use ratatui::{
 };
 use std::io;
 
-use crate::garden::MainGameState;
+use crate::economy;
+use crate::garden::{MainGameState, HOME_PLOT};
 
 pub fn draw_ui(game_state: &mut MainGameState) -> io::Result<()> {
     // setup terminal
@@ -43,18 +44,28 @@ pub fn draw_ui(game_state: &mut MainGameState) -> io::Result<()> {
 }
 
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, game_state: &mut MainGameState) -> io::Result<()> {
+    // Index into the plots in key order; Tab/n and BackTab/p cycle through them.
+    let mut selected_plot = game_state
+        .plots
+        .keys()
+        .position(|key| *key == HOME_PLOT)
+        .unwrap_or(0);
     loop {
-        terminal.draw(|f| ui(f, game_state))?;
+        terminal.draw(|f| ui(f, game_state, selected_plot))?;
 
         if let Event::Key(key) = event::read()? {
-            if let KeyCode::Char('q') = key.code {
-                return Ok(());
+            let plot_count = game_state.plots.len().max(1);
+            match key.code {
+                KeyCode::Char('q') => return Ok(()),
+                KeyCode::Tab | KeyCode::Char('n') => selected_plot = (selected_plot + 1) % plot_count,
+                KeyCode::BackTab | KeyCode::Char('p') => selected_plot = (selected_plot + plot_count - 1) % plot_count,
+                _ => {}
             }
         }
     }
 }
 
-fn ui<B: Backend>(f: &mut Frame<B>, game_state: &MainGameState) {
+fn ui<B: Backend>(f: &mut Frame<B>, game_state: &MainGameState, selected_plot: usize) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
@@ -67,7 +78,13 @@ fn ui<B: Backend>(f: &mut Frame<B>, game_state: &MainGameState) {
         )
         .split(f.size());
 
-    let garden_view = if let Some(plot) = game_state.plots.get(&(0, 0)) {
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+        .split(chunks[0]);
+
+    let plot = game_state.plots.values().nth(selected_plot);
+    let garden_view = if let Some(plot) = plot {
         let mut garden_str = String::new();
         for row in &plot.grid.tiles {
             for tile in row {
@@ -79,11 +96,12 @@ fn ui<B: Backend>(f: &mut Frame<B>, game_state: &MainGameState) {
                         crate::plant::LifeCycleStage::Mature => 'M',
                         crate::plant::LifeCycleStage::Fruiting => 'F',
                         crate::plant::LifeCycleStage::Withering => 'x',
+                        crate::plant::LifeCycleStage::Dead => 'd',
                     },
-                    None => '.',
+                    None => weed_symbol(tile.soil.weeds),
                 };
                 garden_str.push(symbol);
-                garden_str.push(' ');
+                garden_str.push(if tile.plant.is_some() { weed_marker(tile.soil.weeds) } else { ' ' });
             }
             garden_str.push('\n');
         }
@@ -92,15 +110,72 @@ fn ui<B: Backend>(f: &mut Frame<B>, game_state: &MainGameState) {
         Paragraph::new("No plot found.")
     };
 
-    let garden_block = Block::default().title("Garden View").borders(Borders::ALL);
-    f.render_widget(garden_view.block(garden_block), chunks[0]);
+    let garden_title = match plot {
+        Some(plot) => format!(
+            "Garden View - Plot {} [{}/{}] (Tab to switch)",
+            plot.label(),
+            selected_plot + 1,
+            game_state.plots.len()
+        ),
+        None => "Garden View".to_string(),
+    };
+    let garden_block = Block::default().title(garden_title).borders(Borders::ALL);
+    f.render_widget(garden_view.block(garden_block), top[0]);
+
+    let market_block = Block::default().title("Market - what you hold").borders(Borders::ALL);
+    f.render_widget(Paragraph::new(market_panel(game_state)).block(market_block), top[1]);
 
     let status_text = format!(
-        "Tick: {} | Money: ${:.2}",
-        game_state.tick_counter, game_state.wallet
+        "Tick: {} | {:?} | {:?} | Money: ${}",
+        game_state.tick_counter,
+        crate::weather::Season::from_tick(game_state.tick_counter),
+        game_state.current_weather,
+        game_state.wallet
     );
     let status_paragraph = Paragraph::new(status_text);
 
     let status_block = Block::default().title("Command/Status Bar").borders(Borders::ALL);
     f.render_widget(status_paragraph.block(status_block), chunks[1]);
 }
+
+/// Ticks of price history the market panel charts.
+const PANEL_HISTORY_TICKS: usize = 20;
+
+/// The price, recent trend and its range and mean for everything in the
+/// inventory the market trades.
+pub fn market_panel(game_state: &MainGameState) -> String {
+    let mut panel = String::new();
+    for (item, quantity) in game_state.inventory.iter().filter(|(_, quantity)| **quantity > 0) {
+        let Some(price) = game_state.market.price(item) else { continue };
+        let prices = economy::recent_prices(&game_state.market, item, PANEL_HISTORY_TICKS).unwrap_or_default();
+        panel.push_str(&format!("{} x{}  {:.2}  {}", item, quantity, price, economy::sparkline(&prices)));
+        if !prices.is_empty() {
+            panel.push_str(&format!("  {}", economy::price_summary(&prices)));
+        }
+        panel.push('\n');
+    }
+    if panel.is_empty() {
+        panel.push_str("Nothing to sell yet; harvest something to follow its price here.");
+    }
+    panel
+}
+
+/// The symbol for an unplanted tile: '.' when clear, ',' for light weeds and 'w' for a weed patch.
+pub fn weed_symbol(weeds: f32) -> char {
+    if weeds >= 0.5 {
+        'w'
+    } else if weeds >= 0.1 {
+        ','
+    } else {
+        '.'
+    }
+}
+
+/// What follows a planted tile's symbol: the weed symbol if weeds are
+/// growing among the plant, so "P," or "Pw", and a blank otherwise.
+pub fn weed_marker(weeds: f32) -> char {
+    match weed_symbol(weeds) {
+        '.' => ' ',
+        symbol => symbol,
+    }
+}
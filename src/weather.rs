@@ -1,9 +1,53 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum Weather {
     Sunny,
     Cloudy,
     Rainy,
     Heatwave,
 }
+
+/// Ticks in each season; the year starts in spring at tick 0.
+pub const TICKS_PER_SEASON: u64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn from_tick(tick: u64) -> Season {
+        match (tick / TICKS_PER_SEASON) % 4 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    /// Hours of daylight in a day of this season.
+    pub fn daylight_hours(&self) -> f32 {
+        match self {
+            Season::Spring => 12.0,
+            Season::Summer => 15.0,
+            Season::Autumn => 11.0,
+            Season::Winter => 9.0,
+        }
+    }
+}
+
+impl Weather {
+    /// Share of daylight that reaches the ground as direct sun.
+    pub fn sun_fraction(&self) -> f32 {
+        match self {
+            Weather::Sunny => 0.7,
+            Weather::Heatwave => 0.75,
+            Weather::Cloudy => 0.35,
+            Weather::Rainy => 0.25,
+        }
+    }
+}